@@ -1,6 +1,9 @@
+use std::future::Future;
+use std::pin::Pin;
+
 use nom::IResult;
 
-use crate::CommandError;
+use crate::{BoundCommand, CommandError};
 
 mod argument_impl;
 
@@ -159,3 +162,128 @@ pub trait Execute<S, U> {
 pub trait Propagate<S, T, U> {
     fn propagate<'a>(&self, source: S, input: &'a str, data: T) -> IResult<&'a str, U, CommandError<'a>>;
 }
+
+/// Command parser parse-only entrypoint.
+///
+/// Parses the input without running any command logic. The returned
+/// [`BoundCommand`] holds the parsed arguments and can be executed later.
+pub trait ParseCommand<S, U> {
+    fn parse_command<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, BoundCommand<'_, S, U>, CommandError<'a>>;
+}
+
+/// Command parser parse-only propagation entrypoint.
+///
+/// Generally not used by the end user.
+pub trait ParsePropagate<S, T, U> {
+    fn parse_propagate<'a>(&self, source: S, input: &'a str, data: T) -> IResult<&'a str, BoundCommand<'_, S, U>, CommandError<'a>>;
+}
+
+/// Future returned by asynchronous command parsers.
+///
+/// Parsing happens synchronously, this future only runs the command logic
+/// that was selected by the parser.
+pub type CommandFuture<U> = Pin<Box<dyn Future<Output = Result<U, anyhow::Error>> + Send>>;
+
+/// Asynchronous command logic builder type (without propagation).
+///
+/// Counterpart of [`BuildExecute`] for [`AsyncTaskLogicNoArgs`] and
+/// [`AsyncTaskLogic`].
+pub trait BuildAsyncExecute<C, O> {
+    fn build_async_exec(self, task: C) -> O;
+}
+
+/// Asynchronous command logic builder type (with propagation).
+///
+/// Counterpart of [`BuildPropagate`] for [`AsyncTaskLogic`].
+pub trait BuildAsyncPropagate<C, T, O> {
+    fn build_async_propagate(self, task: C) -> O;
+}
+
+/// Asynchronous command logic definition trait (without arguments).
+///
+/// `Fn`-closures returning a future implement this trait.
+pub trait AsyncTaskLogicNoArgs<S> {
+    /// Error type this logic may return
+    type Error: Into<anyhow::Error>;
+    /// Return value upon success
+    type Output;
+    /// Future returned by the logic
+    type Future: Future<Output = Result<Self::Output, Self::Error>> + Send + 'static;
+
+    fn run(&self, source: S) -> Self::Future;
+}
+
+/// Asynchronous command logic definition trait (with arguments).
+///
+/// This type receives propagated arguments from parsers upstream.
+///
+/// `Fn`-closures returning a future implement this trait.
+pub trait AsyncTaskLogic<S, O> {
+    /// Error type this logic may return
+    type Error: Into<anyhow::Error>;
+    /// Return value upon success
+    type Output;
+    /// Future returned by the logic
+    type Future: Future<Output = Result<Self::Output, Self::Error>> + Send + 'static;
+
+    fn run(&self, source: S, args: O) -> Self::Future;
+}
+
+/// Asynchronous command parser execution entrypoint.
+///
+/// The input is parsed immediately, the returned [`CommandFuture`] runs the
+/// command logic when awaited.
+pub trait AsyncExecute<S, U> {
+    fn execute_async<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, CommandFuture<U>, CommandError<'a>>;
+}
+
+/// Asynchronous command parser propagation entrypoint.
+///
+/// Generally not used by the end user.
+pub trait AsyncPropagate<S, T, U> {
+    fn propagate_async<'a>(&self, source: S, input: &'a str, data: T) -> IResult<&'a str, CommandFuture<U>, CommandError<'a>>;
+}
+
+pub(crate) fn box_task<F, U, E>(task: F) -> CommandFuture<U>
+where
+    F: Future<Output = Result<U, E>> + Send + 'static,
+    E: Into<anyhow::Error>,
+{
+    Box::pin(async move { task.await.map_err(Into::into) })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use nom::Finish;
+
+    use crate::parsers::help::ThenHelp;
+    use crate::tests::block_on;
+    use crate::{integer_i32, literal, AsyncCommandParser, BuildAsyncExecute, Then, UsagePrint};
+
+    #[test]
+    fn test_async() {
+        let parser: Box<dyn AsyncCommandParser<i32, i32>> = Box::new(
+            literal("foo")
+                .then(integer_i32("bar").build_async_exec(|x, i| async move { Ok::<i32, Infallible>(x + i) }))
+                .build_async_exec(|x| async move { Ok::<i32, Infallible>(x) })
+                .help("Test description")
+                .build_async_exec(|_, usages: UsagePrint<_>| {
+                    let count = usages.count() as i32;
+                    async move { Ok::<i32, Infallible>(count) }
+                }),
+        );
+
+        let (_, future) = parser.execute_async(10, "foo 5").unwrap();
+        assert_eq!(15, block_on(future).unwrap());
+        let (_, future) = parser.execute_async(10, "foo").unwrap();
+        assert_eq!(10, block_on(future).unwrap());
+        let (_, future) = parser.execute_async(10, "foo help").unwrap();
+        assert_eq!(2, block_on(future).unwrap());
+        let Err(error) = parser.execute_async(10, "foo bar").finish() else {
+            panic!("expected an error for an unknown argument");
+        };
+        assert_eq!("Expected one of: help, <bar>", error.kind.to_string());
+    }
+}
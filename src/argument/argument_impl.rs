@@ -1,11 +1,16 @@
+use std::future::Future;
 use std::marker::PhantomData;
 
 use nom::error::{ErrorKind, FromExternalError};
 use nom::IResult;
 
-use super::{ArgumentMarkerDefaultImpl, BuildExecute, BuildPropagate, CommandArgument, Execute, Propagate, TaskLogic, TaskLogicNoArgs};
+use super::{
+    box_task, ArgumentMarkerDefaultImpl, AsyncExecute, AsyncPropagate, AsyncTaskLogic, AsyncTaskLogicNoArgs, BuildAsyncExecute,
+    BuildAsyncPropagate, BuildExecute, BuildPropagate, CommandArgument, CommandFuture, Execute, Propagate, TaskLogic, TaskLogicNoArgs,
+};
+use crate::bound::Bound;
 use crate::parsers::DefaultExecutor;
-use crate::{CmdErrorKind, CommandError};
+use crate::{BoundCommand, CmdErrorKind, CommandError, ParseCommand, ParsePropagate};
 
 impl<A, O, C, S> CommandArgument<S, O> for DefaultExecutor<A, O, C, S>
 where
@@ -44,6 +49,36 @@ where
     }
 }
 
+impl<S, A, O, C> BuildAsyncExecute<C, DefaultExecutor<A, C, O, S>> for A
+where
+    A: ArgumentMarkerDefaultImpl + CommandArgument<S, O>,
+    C: AsyncTaskLogic<S, O>,
+{
+    fn build_async_exec(self, task: C) -> DefaultExecutor<A, C, O, S> {
+        DefaultExecutor {
+            argument: self,
+            task,
+            output: PhantomData,
+            source: PhantomData,
+        }
+    }
+}
+
+impl<S, A, O, C, T> BuildAsyncPropagate<C, T, DefaultExecutor<A, C, O, S>> for A
+where
+    A: ArgumentMarkerDefaultImpl + CommandArgument<S, O>,
+    C: AsyncTaskLogic<S, (T, O)>,
+{
+    fn build_async_propagate(self, task: C) -> DefaultExecutor<A, C, O, S> {
+        DefaultExecutor {
+            argument: self,
+            task,
+            output: PhantomData,
+            source: PhantomData,
+        }
+    }
+}
+
 impl<A, O, C, U, S> Execute<S, U> for DefaultExecutor<A, C, O, S>
 where
     A: CommandArgument<S, O>,
@@ -53,7 +88,7 @@ where
     fn execute<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, U, CommandError<'a>> {
         let (input, result) = self.argument.parse(source, input)?;
         if !input.is_empty() {
-            return Err(nom::Err::Failure(CommandError::from_external_error(input, ErrorKind::IsNot, CmdErrorKind::NonEmpty)));
+            return Err(nom::Err::Error(CommandError::from_external_error(input, ErrorKind::IsNot, CmdErrorKind::IncorrectArgument)));
         }
         match self.task.run(source, result) {
             Err(e) => Err(nom::Err::Failure(CommandError::from_external_error(input, ErrorKind::MapRes, e))),
@@ -72,7 +107,7 @@ where
     fn propagate<'a>(&self, source: S, input: &'a str, data: T) -> IResult<&'a str, U, CommandError<'a>> {
         let (input, result) = self.argument.parse(source, input)?;
         if !input.is_empty() {
-            return Err(nom::Err::Failure(CommandError::from_external_error(input, ErrorKind::IsNot, CmdErrorKind::NonEmpty)));
+            return Err(nom::Err::Error(CommandError::from_external_error(input, ErrorKind::IsNot, CmdErrorKind::IncorrectArgument)));
         }
         match self.task.run(source, (data, result)) {
             Err(e) => Err(nom::Err::Failure(CommandError::from_external_error(input, ErrorKind::MapRes, e))),
@@ -81,6 +116,82 @@ where
     }
 }
 
+impl<A, O, C, U, S> ParseCommand<S, U> for DefaultExecutor<A, C, O, S>
+where
+    A: CommandArgument<S, O>,
+    C: TaskLogic<S, O, Output = U>,
+    O: Clone + 'static,
+    S: Copy,
+{
+    fn parse_command<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, BoundCommand<'_, S, U>, CommandError<'a>> {
+        let (input, result) = self.argument.parse(source, input)?;
+        if !input.is_empty() {
+            return Err(nom::Err::Error(CommandError::from_external_error(input, ErrorKind::IsNot, CmdErrorKind::IncorrectArgument)));
+        }
+        Ok((
+            input,
+            BoundCommand::new(Bound {
+                task: &self.task,
+                args: result,
+            }),
+        ))
+    }
+}
+
+impl<A, O, C, T, U, S> ParsePropagate<S, T, U> for DefaultExecutor<A, C, O, S>
+where
+    T: Copy + 'static,
+    S: Copy,
+    A: CommandArgument<S, O>,
+    C: TaskLogic<S, (T, O), Output = U>,
+    O: Clone + 'static,
+{
+    fn parse_propagate<'a>(&self, source: S, input: &'a str, data: T) -> IResult<&'a str, BoundCommand<'_, S, U>, CommandError<'a>> {
+        let (input, result) = self.argument.parse(source, input)?;
+        if !input.is_empty() {
+            return Err(nom::Err::Error(CommandError::from_external_error(input, ErrorKind::IsNot, CmdErrorKind::IncorrectArgument)));
+        }
+        Ok((
+            input,
+            BoundCommand::new(Bound {
+                task: &self.task,
+                args: (data, result),
+            }),
+        ))
+    }
+}
+
+impl<A, O, C, U, S> AsyncExecute<S, U> for DefaultExecutor<A, C, O, S>
+where
+    A: CommandArgument<S, O>,
+    C: AsyncTaskLogic<S, O, Output = U>,
+    S: Copy,
+{
+    fn execute_async<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, CommandFuture<U>, CommandError<'a>> {
+        let (input, result) = self.argument.parse(source, input)?;
+        if !input.is_empty() {
+            return Err(nom::Err::Error(CommandError::from_external_error(input, ErrorKind::IsNot, CmdErrorKind::IncorrectArgument)));
+        }
+        Ok((input, box_task(self.task.run(source, result))))
+    }
+}
+
+impl<A, O, C, T, U, S> AsyncPropagate<S, T, U> for DefaultExecutor<A, C, O, S>
+where
+    T: Copy,
+    S: Copy,
+    A: CommandArgument<S, O>,
+    C: AsyncTaskLogic<S, (T, O), Output = U>,
+{
+    fn propagate_async<'a>(&self, source: S, input: &'a str, data: T) -> IResult<&'a str, CommandFuture<U>, CommandError<'a>> {
+        let (input, result) = self.argument.parse(source, input)?;
+        if !input.is_empty() {
+            return Err(nom::Err::Error(CommandError::from_external_error(input, ErrorKind::IsNot, CmdErrorKind::IncorrectArgument)));
+        }
+        Ok((input, box_task(self.task.run(source, (data, result)))))
+    }
+}
+
 impl<E, F, S, O, U> TaskLogic<S, O> for F
 where
     F: Fn(S, O) -> Result<U, E>,
@@ -102,3 +213,29 @@ where
 
     fn run(&self, source: S) -> Result<U, Self::Error> { self(source) }
 }
+
+impl<E, F, R, S, O, U> AsyncTaskLogic<S, O> for F
+where
+    F: Fn(S, O) -> R,
+    R: Future<Output = Result<U, E>> + Send + 'static,
+    E: Into<anyhow::Error>,
+{
+    type Error = E;
+    type Future = R;
+    type Output = U;
+
+    fn run(&self, source: S, args: O) -> R { self(source, args) }
+}
+
+impl<E, F, R, U, S> AsyncTaskLogicNoArgs<S> for F
+where
+    F: Fn(S) -> R,
+    R: Future<Output = Result<U, E>> + Send + 'static,
+    E: Into<anyhow::Error>,
+{
+    type Error = E;
+    type Future = R;
+    type Output = U;
+
+    fn run(&self, source: S) -> R { self(source) }
+}
@@ -0,0 +1,128 @@
+use std::any::Any;
+use std::fmt::Debug;
+
+use crate::{TaskLogic, TaskLogicNoArgs};
+
+/// Command logic bound to its parsed arguments.
+///
+/// Returned by [`ParseCommand`](crate::ParseCommand), this type owns every
+/// argument that was parsed and borrows the command logic from the parser. It
+/// can be stored and executed any number of times without parsing the input
+/// again.
+pub struct BoundCommand<'p, S, U> {
+    task: Box<dyn BoundTask<S, U> + 'p>,
+}
+
+impl<'p, S, U> BoundCommand<'p, S, U> {
+    pub(crate) fn new<B>(task: B) -> Self
+    where
+        B: BoundTask<S, U> + 'p,
+    {
+        BoundCommand {
+            task: Box::new(task),
+        }
+    }
+
+    /// Run the command logic with the bound arguments.
+    pub fn execute(&self, source: S) -> Result<U, anyhow::Error> { self.task.run(source) }
+
+    /// Returns the bound arguments if they are of type `O`, e.g. `(i32,
+    /// bool)` for a command with an integer and a bool argument. Commands
+    /// without arguments bind `()`.
+    pub fn args<O: 'static>(&self) -> Option<&O> { self.task.args().downcast_ref() }
+
+    /// Type name of the bound arguments, only meant for debugging.
+    pub fn args_type_name(&self) -> &'static str { self.task.args_type_name() }
+}
+
+impl<S, U> Debug for BoundCommand<'_, S, U> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BoundCommand")
+            .field("args", &self.args_type_name())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Command logic that has received all of its arguments.
+pub(crate) trait BoundTask<S, U> {
+    fn run(&self, source: S) -> Result<U, anyhow::Error>;
+
+    fn args(&self) -> &dyn Any;
+
+    fn args_type_name(&self) -> &'static str;
+}
+
+/// [`TaskLogic`] together with its arguments.
+pub(crate) struct Bound<'p, C, O> {
+    pub(crate) task: &'p C,
+    pub(crate) args: O,
+}
+
+impl<C, O, S> BoundTask<S, C::Output> for Bound<'_, C, O>
+where
+    C: TaskLogic<S, O>,
+    O: Clone + 'static,
+{
+    fn run(&self, source: S) -> Result<C::Output, anyhow::Error> { self.task.run(source, self.args.clone()).map_err(Into::into) }
+
+    fn args(&self) -> &dyn Any { &self.args }
+
+    fn args_type_name(&self) -> &'static str { std::any::type_name::<O>() }
+}
+
+/// [`TaskLogicNoArgs`] that needs no arguments to be bound.
+pub(crate) struct BoundNoArgs<'p, C> {
+    pub(crate) task: &'p C,
+}
+
+impl<C, S> BoundTask<S, C::Output> for BoundNoArgs<'_, C>
+where
+    C: TaskLogicNoArgs<S>,
+{
+    fn run(&self, source: S) -> Result<C::Output, anyhow::Error> { self.task.run(source).map_err(Into::into) }
+
+    fn args(&self) -> &dyn Any { &() }
+
+    fn args_type_name(&self) -> &'static str { std::any::type_name::<()>() }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use nom::Finish;
+
+    use crate::{boolean, integer_i32, literal, BuildExecute, BuildPropagate, ParseCommand, Then};
+
+    #[test]
+    fn test_parse_command() {
+        let parser = literal("foo")
+            .then(
+                integer_i32("bar")
+                    .then(boolean("buzz").build_propagate(|x: i32, (i, b)| {
+                        Ok::<i32, Infallible>(if b {
+                            x + i
+                        } else {
+                            x - i
+                        })
+                    }))
+                    .build_exec(|x: i32, i| Ok::<i32, Infallible>(x * i)),
+            )
+            .build_exec(|x: i32| Ok::<i32, Infallible>(x));
+
+        let (_, command) = parser.parse_command(1, "foo 5 true").unwrap();
+        assert_eq!(6, command.execute(1).unwrap());
+        assert_eq!(Some(&(5, true)), command.args::<(i32, bool)>());
+        assert_eq!(None, command.args::<i32>());
+        assert!(format!("{:?}", command).contains("(i32, bool)"));
+        assert_eq!(15, command.execute(10).unwrap());
+        let (_, command) = parser.parse_command(1, "foo 5").unwrap();
+        assert_eq!(10, command.execute(2).unwrap());
+        let (_, command) = parser.parse_command(1, "foo").unwrap();
+        assert_eq!(7, command.execute(7).unwrap());
+        assert_eq!(
+            "Invalid bool, expected true or false but found 'what'",
+            parser.parse_command(1, "foo 5 what").finish().unwrap_err().kind.to_string()
+        );
+    }
+}
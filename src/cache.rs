@@ -0,0 +1,227 @@
+use std::collections::HashMap;
+
+use nom::error::{ErrorKind, FromExternalError};
+use nom::Finish;
+
+use crate::{BoundCommand, CmdErrorKind, CommandError, ParseCommand};
+
+/// Cache of parsed commands keyed by their input string.
+///
+/// Commands that are executed over and over (e.g. by command blocks) only get
+/// parsed the first time, every subsequent call reuses the cached
+/// [`BoundCommand`]. Replacing the parser with [`set_parser`] clears the cache.
+///
+/// Only successfully parsed commands are cached. The cache holds at most
+/// [`capacity`](CommandCache::capacity) commands, when it is full the least
+/// recently used command is evicted.
+///
+/// # Note
+/// Cached commands are parsed with the source of the first call, parsers that
+/// depend on the source to parse their arguments should not be cached.
+///
+/// [`set_parser`]: CommandCache::set_parser
+pub struct CommandCache<'p, P: ?Sized, S, U> {
+    parser: &'p P,
+    /// Position of every cached input in `entries`.
+    index: HashMap<String, usize>,
+    /// Cached commands, linked from the most to the least recently used.
+    entries: Vec<CacheEntry<'p, S, U>>,
+    head: usize,
+    tail: usize,
+    capacity: usize,
+}
+
+struct CacheEntry<'p, S, U> {
+    input: String,
+    command: BoundCommand<'p, S, U>,
+    prev: usize,
+    next: usize,
+}
+
+/// End of the list of cache entries.
+const NIL: usize = usize::MAX;
+
+impl<'p, P, S, U> CommandCache<'p, P, S, U>
+where
+    P: ParseCommand<S, U> + ?Sized,
+{
+    /// Capacity of caches created with [`new`](CommandCache::new).
+    pub const DEFAULT_CAPACITY: usize = 256;
+
+    /// Create a new empty cache for `parser` holding at most
+    /// [`DEFAULT_CAPACITY`](CommandCache::DEFAULT_CAPACITY) commands.
+    pub fn new(parser: &'p P) -> Self { Self::with_capacity(parser, Self::DEFAULT_CAPACITY) }
+
+    /// Create a new empty cache for `parser` holding at most `capacity`
+    /// commands.
+    ///
+    /// # Panics
+    /// If `capacity` is 0.
+    pub fn with_capacity(parser: &'p P, capacity: usize) -> Self {
+        assert!(capacity > 0, "cache capacity must be at least 1");
+        CommandCache {
+            parser,
+            index: HashMap::new(),
+            entries: Vec::new(),
+            head: NIL,
+            tail: NIL,
+            capacity,
+        }
+    }
+
+    /// Return the cached command for `input`, parsing it first if it is not
+    /// in the cache yet.
+    pub fn parse<'a>(&mut self, source: S, input: &'a str) -> Result<&BoundCommand<'p, S, U>, CommandError<'a>> {
+        if let Some(&n) = self.index.get(input) {
+            if self.head != n {
+                self.unlink(n);
+                self.push_front(n);
+            }
+            return Ok(&self.entries[n].command);
+        }
+        let (rest, command) = self.parser.parse_command(source, input).finish()?;
+        if !rest.is_empty() {
+            return Err(CommandError::from_external_error(rest, ErrorKind::IsNot, CmdErrorKind::IncorrectArgument));
+        }
+        let n = if self.entries.len() < self.capacity {
+            self.entries.push(CacheEntry {
+                input: input.to_owned(),
+                command,
+                prev: NIL,
+                next: NIL,
+            });
+            self.entries.len() - 1
+        } else {
+            // Reuse the entry of the least recently used command.
+            let n = self.tail;
+            self.unlink(n);
+            let entry = &mut self.entries[n];
+            self.index.remove(&entry.input);
+            entry.input = input.to_owned();
+            entry.command = command;
+            n
+        };
+        self.index.insert(input.to_owned(), n);
+        self.push_front(n);
+        Ok(&self.entries[n].command)
+    }
+
+    /// Remove entry `n` from the list.
+    fn unlink(&mut self, n: usize) {
+        let (prev, next) = (self.entries[n].prev, self.entries[n].next);
+        self.link(prev, next);
+    }
+
+    /// Make `next` follow `prev` in the list.
+    fn link(&mut self, prev: usize, next: usize) {
+        match prev {
+            NIL => self.head = next,
+            prev => self.entries[prev].next = next,
+        }
+        match next {
+            NIL => self.tail = prev,
+            next => self.entries[next].prev = prev,
+        }
+    }
+
+    /// Insert entry `n` as the most recently used one.
+    fn push_front(&mut self, n: usize) {
+        let head = self.head;
+        self.entries[n].prev = NIL;
+        self.entries[n].next = head;
+        self.link(n, head);
+        self.head = n;
+    }
+
+    /// Parse `input` (or reuse the cached command) and execute it.
+    pub fn execute<'a>(&mut self, source: S, input: &'a str) -> Result<U, CommandError<'a>>
+    where
+        S: Copy,
+    {
+        let command = self.parse(source, input)?;
+        command
+            .execute(source)
+            .map_err(|e| CommandError::from_external_error(&input[input.len()..], ErrorKind::MapRes, e))
+    }
+
+    /// Replace the parser, this invalidates every cached command.
+    pub fn set_parser(&mut self, parser: &'p P) {
+        self.parser = parser;
+        self.clear();
+    }
+
+    /// Remove the cached command for `input`.
+    pub fn invalidate(&mut self, input: &str) -> bool {
+        let Some(n) = self.index.remove(input) else {
+            return false;
+        };
+        self.unlink(n);
+        self.entries.swap_remove(n);
+        if n < self.entries.len() {
+            // The last entry moved to `n`.
+            let (prev, next) = (self.entries[n].prev, self.entries[n].next);
+            self.link(prev, n);
+            self.link(n, next);
+            *self.index.get_mut(&self.entries[n].input).expect("moved entry is indexed") = n;
+        }
+        true
+    }
+
+    /// Remove every cached command.
+    pub fn clear(&mut self) {
+        self.index.clear();
+        self.entries.clear();
+        self.head = NIL;
+        self.tail = NIL;
+    }
+
+    /// Number of cached commands.
+    pub fn len(&self) -> usize { self.entries.len() }
+
+    /// Returns true if no commands are cached.
+    pub fn is_empty(&self) -> bool { self.entries.is_empty() }
+
+    /// Maximum number of cached commands.
+    pub fn capacity(&self) -> usize { self.capacity }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use crate::{integer_i32, literal, BuildExecute, CommandCache, ParseCommand, Then};
+
+    #[test]
+    fn test_cache() {
+        let first = literal("foo").then(integer_i32("bar").build_exec(|x: i32, i| Ok::<i32, Infallible>(x + i)));
+        let second = literal("foo").then(integer_i32("bar").build_exec(|x: i32, i| Ok::<i32, Infallible>(x * i)));
+
+        let mut cache: CommandCache<dyn ParseCommand<i32, i32>, _, _> = CommandCache::new(&first);
+        assert_eq!(7, cache.execute(2, "foo 5").unwrap());
+        assert_eq!(8, cache.execute(3, "foo 5").unwrap());
+        assert_eq!(1, cache.len());
+        assert_eq!("Incomplete command", cache.execute(3, "foo").unwrap_err().kind.to_string());
+        assert_eq!("Incorrect argument for command", cache.execute(3, "foo 5 6").unwrap_err().kind.to_string());
+        assert_eq!(1, cache.len());
+
+        cache.set_parser(&second);
+        assert!(cache.is_empty());
+        assert_eq!(15, cache.execute(3, "foo 5").unwrap());
+
+        let mut cache: CommandCache<dyn ParseCommand<i32, i32>, _, _> = CommandCache::with_capacity(&first, 2);
+        assert_eq!(6, cache.execute(1, "foo 5").unwrap());
+        assert_eq!(7, cache.execute(1, "foo 6").unwrap());
+        assert_eq!(6, cache.execute(1, "foo 5").unwrap());
+        assert_eq!(8, cache.execute(1, "foo 7").unwrap());
+        assert_eq!(2, cache.len());
+        assert!(!cache.invalidate("foo 6"));
+        assert!(cache.invalidate("foo 5"));
+        assert_eq!(9, cache.execute(1, "foo 8").unwrap());
+        assert_eq!(10, cache.execute(1, "foo 9").unwrap());
+        assert_eq!(2, cache.len());
+        assert!(!cache.invalidate("foo 7"));
+        assert!(cache.invalidate("foo 8"));
+        assert!(cache.invalidate("foo 9"));
+        assert!(cache.is_empty());
+    }
+}
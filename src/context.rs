@@ -0,0 +1,122 @@
+use std::any::Any;
+use std::borrow::Cow;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use nom::IResult;
+
+use crate::{CommandArgument, CommandError};
+
+/// Type-erased arguments stored by name.
+///
+/// This is an alternative to the typed tuples of propagating parsers, for
+/// commands whose structure is only known at runtime. Arguments are parsed
+/// with any [`CommandArgument`] and retrieved by name and type:
+/// ```
+/// # use brigadier_rs::{integer_i32, CommandContext};
+/// let mut ctx = CommandContext::new();
+/// ctx.parse("bar", &integer_i32("bar"), (), "42").unwrap();
+/// assert_eq!(Some(&42), ctx.get::<i32>("bar"));
+/// ```
+#[derive(Clone, Default)]
+pub struct CommandContext {
+    path: Vec<Cow<'static, str>>,
+    arguments: Vec<(Cow<'static, str>, Arc<dyn Any + Send + Sync>)>,
+}
+
+impl CommandContext {
+    /// Create an empty context.
+    pub fn new() -> Self { Self::default() }
+
+    /// Parse an argument from `input` using `argument` and store it as
+    /// `name`.
+    pub fn parse<'a, S, O, A>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        argument: &A,
+        source: S,
+        input: &'a str,
+    ) -> IResult<&'a str, (), CommandError<'a>>
+    where
+        A: CommandArgument<S, O> + ?Sized,
+        O: Any + Send + Sync,
+    {
+        let (input, value) = argument.parse(source, input)?;
+        self.insert(name, value);
+        Ok((input, ()))
+    }
+
+    /// Store `value` as the argument `name`, this shadows any previous value
+    /// of that name.
+    pub fn insert<T: Any + Send + Sync>(&mut self, name: impl Into<Cow<'static, str>>, value: T) {
+        let name = name.into();
+        self.path.push(name.clone());
+        self.arguments.push((name, Arc::new(value)));
+    }
+
+    /// Returns the argument `name`, or `None` if there is no argument with
+    /// that name and type.
+    pub fn get<T: Any>(&self, name: &str) -> Option<&T> {
+        self.arguments
+            .iter()
+            .rev()
+            .find(|(n, _)| n == name)
+            .and_then(|(_, v)| v.downcast_ref())
+    }
+
+    /// Returns true if there is an argument `name` of any type.
+    pub fn contains(&self, name: &str) -> bool { self.arguments.iter().any(|(n, _)| n == name) }
+
+    /// Names of every literal and argument on the parsed path, starting with
+    /// the root.
+    pub fn path(&self) -> &[Cow<'static, str>] { &self.path }
+
+    /// Amount of stored arguments.
+    pub fn len(&self) -> usize { self.arguments.len() }
+
+    /// Returns true if no arguments are stored.
+    pub fn is_empty(&self) -> bool { self.arguments.is_empty() }
+
+    pub(crate) fn push_literal(&mut self, name: Cow<'static, str>) { self.path.push(name); }
+
+    /// Undo everything after a [`mark`](Self::mark).
+    pub(crate) fn reset(&mut self, (path, arguments): (usize, usize)) {
+        self.path.truncate(path);
+        self.arguments.truncate(arguments);
+    }
+
+    pub(crate) fn mark(&self) -> (usize, usize) { (self.path.len(), self.arguments.len()) }
+}
+
+impl Debug for CommandContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CommandContext")
+            .field("path", &self.path)
+            .field("arguments", &self.arguments.iter().map(|(n, _)| n).collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::Finish;
+
+    use crate::{boolean, integer_i32, CommandContext};
+
+    #[test]
+    fn test_command_context() {
+        let mut ctx = CommandContext::new();
+        let (input, _) = ctx.parse("bar", &integer_i32("bar"), (), "5 true").unwrap();
+        let (input, _) = ctx.parse("flag", &boolean("flag"), (), &input[1..]).unwrap();
+        assert!(input.is_empty());
+        ctx.insert("name", String::from("foo"));
+
+        assert_eq!(Some(&5), ctx.get::<i32>("bar"));
+        assert_eq!(Some(&true), ctx.get::<bool>("flag"));
+        assert_eq!(Some("foo"), ctx.get::<String>("name").map(String::as_str));
+        assert_eq!(None, ctx.get::<i64>("bar"));
+        assert_eq!(None, ctx.get::<i32>("baz"));
+        assert_eq!(3, ctx.len());
+        assert_eq!("Expected integer", ctx.parse("baz", &integer_i32("baz"), (), "x").finish().unwrap_err().kind.to_string());
+    }
+}
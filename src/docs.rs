@@ -0,0 +1,171 @@
+use std::borrow::Cow;
+use std::fmt::{Error, Write};
+
+use crate::{HelpEntry, HelpUsage, IntoMultipleUsage, MultipleUsage};
+
+/// Output format of [`CommandDocs`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocFormat {
+    Markdown,
+    Html,
+}
+
+/// Reference documentation generated from parsers.
+///
+/// Every registered command gets a section with its [`HelpEntry`] and a table
+/// of all its syntaxes (including argument types and bounds) together with
+/// their descriptions, see [`Describe`](crate::Describe).
+#[derive(Debug, Clone)]
+pub struct CommandDocs {
+    title: Cow<'static, str>,
+    sections: Vec<DocSection>,
+}
+
+#[derive(Debug, Clone)]
+struct DocSection {
+    entry: HelpEntry,
+    usages: Vec<(String, Option<&'static str>)>,
+}
+
+impl CommandDocs {
+    /// Create empty documentation with a page title.
+    pub fn new(title: impl Into<Cow<'static, str>>) -> Self {
+        CommandDocs {
+            title: title.into(),
+            sections: Vec::new(),
+        }
+    }
+
+    /// Add a section for `parser`.
+    pub fn command<P>(&mut self, parser: &P) -> Result<&mut Self, Error>
+    where
+        P: HelpUsage + IntoMultipleUsage + ?Sized,
+    {
+        let usages = parser
+            .usage_gen()
+            .string_iter()
+            .typed()
+            .with_descriptions()
+            .collect::<Result<_, _>>()?;
+        self.sections.push(DocSection {
+            entry: parser.help(),
+            usages,
+        });
+        Ok(self)
+    }
+
+    /// Write the documentation in the given format, sections are sorted by
+    /// command name.
+    pub fn write<W: Write>(&self, writer: &mut W, format: DocFormat) -> Result<(), Error> {
+        let mut sections: Vec<_> = self.sections.iter().collect();
+        sections.sort_by_cached_key(|s| s.entry.name.to_lowercase());
+        match format {
+            DocFormat::Markdown => self.write_markdown(writer, &sections),
+            DocFormat::Html => self.write_html(writer, &sections),
+        }
+    }
+
+    /// Returns the documentation in the given format.
+    pub fn render(&self, format: DocFormat) -> String {
+        let mut result = String::new();
+        self.write(&mut result, format).expect("writing to a String cannot fail");
+        result
+    }
+
+    fn write_markdown<W: Write>(&self, writer: &mut W, sections: &[&DocSection]) -> Result<(), Error> {
+        writeln!(writer, "# {}", escape_markdown(&self.title))?;
+        for section in sections {
+            let entry = &section.entry;
+            writeln!(writer, "\n## {}\n\n{}\n", escape_markdown(&entry.name), escape_markdown(&entry.description))?;
+            writeln!(writer, "| Syntax | Description |\n| --- | --- |")?;
+            for (usage, description) in &section.usages {
+                // Code spans are not parsed as HTML, only the table separator needs escaping.
+                writeln!(writer, "| `{}` | {} |", usage.replace('|', "\\|"), escape_markdown(description.unwrap_or_default()))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_html<W: Write>(&self, writer: &mut W, sections: &[&DocSection]) -> Result<(), Error> {
+        writeln!(writer, "<h1>{}</h1>", escape_html(&self.title))?;
+        for section in sections {
+            let name = escape_html(&section.entry.name);
+            writeln!(writer, "<section id=\"{}\">\n<h2>{}</h2>", name, name)?;
+            writeln!(writer, "<p>{}</p>", escape_html(&section.entry.description))?;
+            writeln!(writer, "<table>\n<tr><th>Syntax</th><th>Description</th></tr>")?;
+            for (usage, description) in &section.usages {
+                writeln!(
+                    writer,
+                    "<tr><td><code>{}</code></td><td>{}</td></tr>",
+                    escape_html(usage),
+                    escape_html(description.unwrap_or_default())
+                )?;
+            }
+            writeln!(writer, "</table>\n</section>")?;
+        }
+        Ok(())
+    }
+}
+
+/// Escape HTML and the table separator `|` in Markdown text.
+fn escape_markdown(text: &str) -> Cow<'_, str> {
+    match escape_html(text) {
+        Cow::Borrowed(text) if !text.contains('|') => Cow::Borrowed(text),
+        text => Cow::Owned(text.replace('|', "\\|")),
+    }
+}
+
+fn escape_html(text: &str) -> Cow<'_, str> {
+    if !text.contains(['&', '<', '>', '"']) {
+        return Cow::Borrowed(text);
+    }
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            c => result.push(c),
+        }
+    }
+    Cow::Owned(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use crate::parsers::help::ThenHelp;
+    use crate::{integer_i32, literal, BuildExecute, CommandDocs, Describe, DocFormat, Execute, Then, UsagePrint};
+
+    #[test]
+    fn test_docs() {
+        let parser = literal("foo")
+            .then(
+                literal("add").then(
+                    integer_i32("bar")
+                        .min(0)
+                        .max(10)
+                        .build_exec(|_: (), _| Ok::<_, Infallible>(()))
+                        .describe("Add a <number>"),
+                ),
+            )
+            .then(literal("clear").build_exec(|_: ()| Ok::<_, Infallible>(())))
+            .help("Manage foo")
+            .build_exec(|_: (), _: UsagePrint<_>| Ok::<_, Infallible>(()));
+
+        parser.execute((), "foo clear").unwrap();
+
+        let mut docs = CommandDocs::new("Commands");
+        docs.command(&parser).unwrap();
+        assert_eq!(
+            "# Commands\n\n## foo\n\nManage foo\n\n| Syntax | Description |\n| --- | --- |\n| `foo add <bar: i32 0..10>` | Add a \
+             &lt;number&gt; |\n| `foo clear` |  |\n",
+            docs.render(DocFormat::Markdown)
+        );
+        assert!(docs
+            .render(DocFormat::Html)
+            .contains("<tr><td><code>foo add &lt;bar: i32 0..10&gt;</code></td><td>Add a &lt;number&gt;</td></tr>"));
+    }
+}
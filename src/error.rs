@@ -1,4 +1,7 @@
+use std::borrow::Cow;
+use std::cmp::Ordering;
 use std::fmt::Display;
+use std::ops::Range;
 
 use nom::error::{ContextError, ErrorKind, FromExternalError, ParseError};
 
@@ -7,18 +10,27 @@ use nom::error::{ContextError, ErrorKind, FromExternalError, ParseError};
 /// This mimics `CommandSyntaxException` from the java version. This error type
 /// can be used with all nom parsers. Anyhow is used to allow returning any
 /// error in execution closures. The type is basically the combination of
-/// remanining input and a [`CmdErrorKind`].
+/// remanining input, the offending token and a [`CmdErrorKind`].
+///
+/// Absolute positions can be retrieved with [`cursor`](CommandError::cursor)
+/// and [`span`](CommandError::span) given the full command input.
 #[derive(Debug)]
 pub struct CommandError<'a> {
     pub input: &'a str,
+    /// Offending token, this is a slice of the command input.
+    pub(crate) token: &'a str,
     pub kind: CmdErrorKind,
+    /// Literals and arguments that were expected at this position.
+    pub expected: Vec<Expected>,
 }
 
 impl<'a> ParseError<&'a str> for CommandError<'a> {
     fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
         CommandError {
             input,
+            token: next_token(input),
             kind: CmdErrorKind::Nom(kind),
+            expected: Vec::new(),
         }
     }
 
@@ -27,8 +39,32 @@ impl<'a> ParseError<&'a str> for CommandError<'a> {
     fn from_char(input: &'a str, c: char) -> Self {
         CommandError {
             input,
+            token: next_token(input),
             kind: CmdErrorKind::Char(c),
+            expected: Vec::new(),
+        }
+    }
+
+    /// Keeps the error that got furthest into the input. Errors at the same
+    /// position are merged, collecting everything that was expected by either
+    /// branch.
+    fn or(self, mut other: Self) -> Self {
+        match self.input.len().cmp(&other.input.len()) {
+            Ordering::Less => return self,
+            Ordering::Greater => return other,
+            Ordering::Equal => {},
         }
+        let mut expected = self.expected;
+        for e in other.expected {
+            if !expected.contains(&e) {
+                expected.push(e);
+            }
+        }
+        if expected.len() > 1 {
+            other.kind = CmdErrorKind::ExpectedOneOf(expected.clone());
+        }
+        other.expected = expected;
+        other
     }
 }
 
@@ -39,7 +75,9 @@ where
     fn from_external_error(input: &'a str, _: ErrorKind, e: E) -> Self {
         CommandError {
             input,
+            token: next_token(input),
             kind: e.into(),
+            expected: Vec::new(),
         }
     }
 }
@@ -48,7 +86,26 @@ impl<'a> ContextError<&'a str> for CommandError<'a> {
     fn add_context(_input: &'a str, _ctx: &'static str, other: Self) -> Self { other }
 }
 
+/// Literal or argument expected by a parser.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expected {
+    Literal(Cow<'static, str>),
+    Argument(Cow<'static, str>),
+}
+
+impl Display for Expected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expected::Literal(literal) => write!(f, "{}", literal),
+            Expected::Argument(name) => write!(f, "<{}>", name),
+        }
+    }
+}
+
 /// Error kinds of [`CommandError`].
+///
+/// Most variants mirror the built-in exceptions of Brigadier's
+/// `CommandSyntaxException` and display the same English message as vanilla.
 #[derive(Debug)]
 pub enum CmdErrorKind {
     /// Nom parser expected a character but found a different one instead.
@@ -57,10 +114,85 @@ pub enum CmdErrorKind {
     External(anyhow::Error),
     /// Any other nom error.
     Nom(ErrorKind),
-    /// Can be returned when parsing number arguments
-    OutOfBounds,
-    /// Input is not empty
-    NonEmpty,
+    /// Literal argument did not match.
+    ExpectedLiteral(Cow<'static, str>),
+    /// Literal argument found a different word.
+    UnknownLiteral {
+        expected: Cow<'static, str>,
+        found: String,
+    },
+    /// Number argument found no number.
+    ExpectedNumber(NumberKind),
+    /// Number argument found a number that does not fit its type.
+    InvalidNumber {
+        number: NumberKind,
+        found: String,
+    },
+    /// Number argument is below its minimum.
+    TooLow {
+        number: NumberKind,
+        min: String,
+        found: String,
+    },
+    /// Number argument is above its maximum.
+    TooHigh {
+        number: NumberKind,
+        max: String,
+        found: String,
+    },
+    /// Boolean argument found no input.
+    ExpectedBool,
+    /// Boolean argument found something other than `true` or `false`.
+    InvalidBool(String),
+    /// Quoted string contains an unknown escape sequence.
+    InvalidEscape(char),
+    /// Quoted string is never closed.
+    UnclosedQuote,
+    /// Can be returned by dispatchers when no command matches the input.
+    UnknownCommand,
+    /// Input is not empty after the command was parsed.
+    IncorrectArgument,
+    /// Input ended before the command was complete.
+    IncompleteCommand,
+    /// Argument is not followed by a separator.
+    ExpectedSeparator,
+    /// Separator is not a single space, see
+    /// [`Separator::Strict`](crate::parsers::Separator::Strict).
+    UnexpectedWhitespace,
+    /// None of the alternative branches matched.
+    ExpectedOneOf(Vec<Expected>),
+}
+
+/// Number types used by [`CmdErrorKind`], named after their Brigadier
+/// counterparts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberKind {
+    Integer,
+    Long,
+    Float,
+    Double,
+}
+
+impl NumberKind {
+    fn title(&self) -> &'static str {
+        match self {
+            NumberKind::Integer => "Integer",
+            NumberKind::Long => "Long",
+            NumberKind::Float => "Float",
+            NumberKind::Double => "Double",
+        }
+    }
+}
+
+impl Display for NumberKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NumberKind::Integer => write!(f, "integer"),
+            NumberKind::Long => write!(f, "long"),
+            NumberKind::Float => write!(f, "float"),
+            NumberKind::Double => write!(f, "double"),
+        }
+    }
 }
 
 impl Display for CmdErrorKind {
@@ -69,8 +201,31 @@ impl Display for CmdErrorKind {
             CmdErrorKind::Char(c) => write!(f, "Expected {}", c),
             CmdErrorKind::External(e) => write!(f, "{}", e.root_cause()),
             CmdErrorKind::Nom(e) => write!(f, "Parse error: {}", e.description()),
-            CmdErrorKind::OutOfBounds => write!(f, "Number was out of bounds"),
-            CmdErrorKind::NonEmpty => write!(f, "Unknown input"),
+            CmdErrorKind::ExpectedLiteral(literal) => write!(f, "Expected literal {}", literal),
+            CmdErrorKind::UnknownLiteral { expected, found } => write!(f, "Unknown literal '{}', expected {}", found, expected),
+            CmdErrorKind::ExpectedNumber(number) => write!(f, "Expected {}", number),
+            CmdErrorKind::InvalidNumber { number, found } => write!(f, "Invalid {} '{}'", number, found),
+            CmdErrorKind::TooLow { number, min, found } => write!(f, "{} must not be less than {}, found {}", number.title(), min, found),
+            CmdErrorKind::TooHigh { number, max, found } => write!(f, "{} must not be more than {}, found {}", number.title(), max, found),
+            CmdErrorKind::ExpectedBool => write!(f, "Expected bool"),
+            CmdErrorKind::InvalidBool(found) => write!(f, "Invalid bool, expected true or false but found '{}'", found),
+            CmdErrorKind::InvalidEscape(c) => write!(f, "Invalid escape sequence '\\{}' in quoted string", c),
+            CmdErrorKind::UnclosedQuote => write!(f, "Unclosed quoted string"),
+            CmdErrorKind::UnknownCommand => write!(f, "Unknown command"),
+            CmdErrorKind::IncorrectArgument => write!(f, "Incorrect argument for command"),
+            CmdErrorKind::IncompleteCommand => write!(f, "Incomplete command"),
+            CmdErrorKind::ExpectedSeparator => write!(f, "Expected whitespace to end one argument, but found trailing data"),
+            CmdErrorKind::UnexpectedWhitespace => write!(f, "Expected a single space between arguments"),
+            CmdErrorKind::ExpectedOneOf(expected) => {
+                write!(f, "Expected one of: ")?;
+                for (i, e) in expected.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", e)?;
+                }
+                Ok(())
+            },
         }
     }
 }
@@ -82,24 +237,345 @@ where
     fn from(e: E) -> Self { CmdErrorKind::External(e.into()) }
 }
 
+pub(crate) fn next_token(input: &str) -> &str {
+    match input.find(char::is_whitespace) {
+        Some(end) => &input[..end],
+        None => input,
+    }
+}
+
+/// Byte and char index into a command input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Hash)]
+pub struct Cursor {
+    pub byte: usize,
+    pub char: usize,
+}
+
+impl Cursor {
+    /// Create a cursor at byte index `byte` of `input`.
+    ///
+    /// `byte` is clamped to the length of `input` and rounded down to a char
+    /// boundary.
+    pub fn new(input: &str, byte: usize) -> Self {
+        let byte = (0..=byte.min(input.len())).rev().find(|b| input.is_char_boundary(*b)).unwrap_or(0);
+        Cursor {
+            byte,
+            char: input[..byte].chars().count(),
+        }
+    }
+}
+
 impl<'a> CommandError<'a> {
-    pub fn convert(&self, input: &'a str, context_size: usize) -> String {
-        let input_len = input.chars().count();
-        let error_len = self.input.chars().count();
-        let start = input
-            .chars()
-            .take((input_len - error_len).saturating_sub(context_size))
-            .map(|c| c.len_utf8())
-            .sum::<usize>();
-        let end = input.len() - self.input.len();
-        let input = &input[start..end];
-
-        let prefix = if input_len - error_len > context_size {
-            "..."
-        } else {
-            ""
+    /// Error for a word at the start of `input` that is none of the
+    /// `expected` literals.
+    pub(crate) fn unknown_literal(input: &'a str, expected: Vec<Expected>) -> Self {
+        let token = next_token(input);
+        let kind = match expected.as_slice() {
+            [] => return CommandError::from_error_kind(input, ErrorKind::Alt),
+            [Expected::Literal(literal)] if !token.is_empty() => CmdErrorKind::UnknownLiteral {
+                expected: literal.clone(),
+                found: token.to_owned(),
+            },
+            [Expected::Literal(literal)] => CmdErrorKind::ExpectedLiteral(literal.clone()),
+            expected => CmdErrorKind::ExpectedOneOf(expected.to_vec()),
+        };
+        let mut error = CommandError::from_external_error(input, ErrorKind::Tag, kind);
+        error.expected = expected;
+        error
+    }
+
+    /// Set what was expected at the position of this error.
+    pub fn with_expected(mut self, expected: Expected) -> Self {
+        self.expected = vec![expected];
+        self
+    }
+
+    /// Offending token, this is a slice of the command input.
+    pub fn token(&self) -> &'a str { self.token }
+
+    /// Set the offending token, `token` must be a slice of the command input.
+    pub fn with_token(mut self, token: &'a str) -> Self {
+        self.token = token;
+        self
+    }
+
+    /// Position at which the error occurred, `input` must be the full command
+    /// input this error was created from.
+    ///
+    /// For any other `input` the position is clamped to a char boundary of
+    /// `input` instead of panicking.
+    pub fn cursor(&self, input: &str) -> Cursor {
+        let byte = match input.strip_suffix(self.input) {
+            Some(before) => before.len(),
+            None => input.len().saturating_sub(self.input.len()),
         };
+        Cursor::new(input, byte)
+    }
+
+    /// Range of the offending token, `input` must be the full command input
+    /// this error was created from.
+    ///
+    /// If the token is not part of `input` the range is empty and starts at
+    /// [`cursor`](CommandError::cursor).
+    pub fn span(&self, input: &str) -> Range<Cursor> {
+        let start = (self.token.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+        let end = start.wrapping_add(self.token.len());
+        if start <= end && input.get(start..end).is_some() {
+            Cursor::new(input, start)..Cursor::new(input, end)
+        } else {
+            let cursor = self.cursor(input);
+            cursor..cursor
+        }
+    }
+
+    pub fn convert(&self, input: &'a str, context_size: usize) -> String {
+        format_error(&self.kind, input, self.cursor(input).byte, context_size)
+    }
+
+    /// Convert into an [`OwnedCommandError`], `input` must be the full command
+    /// input this error was created from.
+    pub fn into_owned(self, input: &str) -> OwnedCommandError {
+        OwnedCommandError {
+            cursor: self.cursor(input),
+            span: self.span(input),
+            kind: self.kind,
+            input: None,
+            remaining: None,
+        }
+    }
+}
+
+/// Owned version of [`CommandError`].
+///
+/// Unlike [`CommandError`], this type does not borrow the command input and can
+/// be stored or sent across threads. The error position is kept as a
+/// [`Cursor`] into the full input.
+#[derive(Debug)]
+pub struct OwnedCommandError {
+    pub cursor: Cursor,
+    /// Range of the offending token.
+    pub span: Range<Cursor>,
+    pub kind: CmdErrorKind,
+    /// Full command input, see [`with_input`](OwnedCommandError::with_input).
+    pub input: Option<String>,
+    /// Only kept until the position in the full input is known.
+    remaining: Option<Remaining>,
+}
+
+/// Remaining input of an error converted without the full input.
+#[derive(Debug)]
+struct Remaining {
+    input: String,
+    token: String,
+    /// Byte offset of the token from the start of `input`, the token can be
+    /// before the remaining input (e.g. a number that is too large).
+    token_offset: isize,
+}
+
+impl OwnedCommandError {
+    /// Attach the full command input to this error.
+    ///
+    /// For errors converted with `From` this also computes `cursor` and
+    /// `span`, as long as `input` ends with the remaining input of the error.
+    pub fn with_input(mut self, input: impl Into<String>) -> Self {
+        let input = input.into();
+        if let Some(remaining) = &self.remaining {
+            let start = input.strip_suffix(remaining.input.as_str()).map(str::len);
+            let token = start.and_then(|start| start.checked_add_signed(remaining.token_offset));
+            let token = token.filter(|token| input.get(*token..).is_some_and(|rest| rest.starts_with(&remaining.token)));
+            if let (Some(start), Some(token)) = (start, token) {
+                self.cursor = Cursor::new(&input, start);
+                self.span = Cursor::new(&input, token)..Cursor::new(&input, token + remaining.token.len());
+                self.remaining = None;
+            }
+        }
+        self.input = Some(input);
+        self
+    }
+
+    /// Same as [`CommandError::convert`], returns `None` if the input or the
+    /// position in it is unknown.
+    pub fn convert(&self, context_size: usize) -> Option<String> {
+        let input = self.located_input()?;
+        Some(format_error(&self.kind, input, self.cursor.byte, context_size))
+    }
+
+    /// Full input, if it is known and `cursor` and `span` point into it.
+    pub(crate) fn located_input(&self) -> Option<&str> {
+        let input = self.input.as_deref().filter(|_| self.remaining.is_none())?;
+        input.get(..self.cursor.byte)?;
+        input.get(self.span.start.byte..self.span.end.byte)?;
+        Some(input)
+    }
+}
+
+/// Converting without the full command input only knows the remaining input,
+/// `cursor` and `span` stay at the start until the full input is attached
+/// with [`with_input`](OwnedCommandError::with_input). Use
+/// [`CommandError::into_owned`] to keep the absolute position instead.
+impl<'a> From<CommandError<'a>> for OwnedCommandError {
+    fn from(error: CommandError<'a>) -> Self {
+        OwnedCommandError {
+            cursor: Cursor::default(),
+            span: Cursor::default()..Cursor::default(),
+            kind: error.kind,
+            input: None,
+            remaining: Some(Remaining {
+                input: error.input.to_owned(),
+                token: error.token.to_owned(),
+                token_offset: (error.token.as_ptr() as isize).wrapping_sub(error.input.as_ptr() as isize),
+            }),
+        }
+    }
+}
+
+impl Display for OwnedCommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.located_input(), &self.remaining) {
+            (Some(input), _) => write!(f, "{}: {}<--[HERE]", self.kind, &input[..self.cursor.byte]),
+            (None, Some(remaining)) => write!(f, "{}: ...<--[HERE]{}", self.kind, remaining.input),
+            (None, None) => write!(f, "{} at position {}", self.kind, self.cursor.char),
+        }
+    }
+}
+
+impl std::error::Error for OwnedCommandError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            CmdErrorKind::External(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+fn format_error(kind: &CmdErrorKind, input: &str, cursor: usize, context_size: usize) -> String {
+    let (prefix, input) = context(input, cursor, context_size);
+    format!("{}: {}{}<--[HERE]", kind, prefix, input)
+}
+
+/// Returns at most `context_size` chars of `input` before `cursor` and
+/// `"..."` if the input was shortened.
+pub(crate) fn context(input: &str, cursor: usize, context_size: usize) -> (&'static str, &str) {
+    let consumed = input[..cursor].chars().count();
+    let start = input
+        .chars()
+        .take(consumed.saturating_sub(context_size))
+        .map(|c| c.len_utf8())
+        .sum::<usize>();
+
+    let prefix = if consumed > context_size {
+        "..."
+    } else {
+        ""
+    };
+
+    (prefix, &input[start..cursor])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use nom::Finish;
+
+    use crate::{boolean, integer_i32, literal, BuildExecute, BuildPropagate, Cursor, Execute, Expected, OwnedCommandError, Then};
+
+    #[test]
+    fn test_owned_error() {
+        fn assert_send_sync<T: Send + Sync + std::error::Error + 'static>(_: &T) {}
+
+        let parser = literal("foo").then(integer_i32("bar").max(10).build_exec(|_: (), _| Ok::<(), Infallible>(())));
+        let input = String::from("foo 13");
+        let error = parser
+            .execute((), &input)
+            .finish()
+            .unwrap_err()
+            .into_owned(&input)
+            .with_input(input.clone());
+        drop(input);
+
+        assert_send_sync(&error);
+        assert_eq!(Cursor { byte: 6, char: 6 }, error.cursor);
+        assert_eq!(Cursor { byte: 4, char: 4 }..Cursor { byte: 6, char: 6 }, error.span);
+        assert_eq!("Integer must not be more than 10, found 13: foo 13<--[HERE]", error.to_string());
+        assert_eq!(Some("Integer must not be more than 10, found 13: ...o 13<--[HERE]".to_owned()), error.convert(4));
+
+        assert_eq!(None, error.with_input("foo").convert(4));
+
+        let error = OwnedCommandError::from(parser.execute((), "foo 13").finish().unwrap_err());
+        assert_eq!(None, error.input);
+        assert_eq!("Integer must not be more than 10, found 13: ...<--[HERE]", error.to_string());
+        let error = error.with_input("foo 13");
+        assert_eq!(Cursor { byte: 4, char: 4 }..Cursor { byte: 6, char: 6 }, error.span);
+        assert_eq!("Integer must not be more than 10, found 13: foo 13<--[HERE]", error.to_string());
+    }
+
+    #[test]
+    fn test_error_cursor() {
+        let parser = literal("föö").then(integer_i32("bar").max(10).build_exec(|_: (), _| Ok::<(), Infallible>(())));
+
+        let input = "föö 13";
+        let error = parser.execute((), input).finish().unwrap_err();
+        assert_eq!(Cursor { byte: 8, char: 6 }, error.cursor(input));
+        assert_eq!(Cursor { byte: 6, char: 4 }..Cursor { byte: 8, char: 6 }, error.span(input));
+
+        let input = "föö abc def";
+        let error = parser.execute((), input).finish().unwrap_err();
+        assert_eq!(Cursor { byte: 6, char: 4 }, error.cursor(input));
+        assert_eq!(Cursor { byte: 6, char: 4 }..Cursor { byte: 9, char: 7 }, error.span(input));
+        assert_eq!(Cursor { byte: 0, char: 0 }, error.cursor("föö"));
+        assert_eq!(Cursor { byte: 0, char: 0 }..Cursor { byte: 0, char: 0 }, error.span("ö123456"));
+        assert_eq!(Cursor { byte: 2, char: 2 }, error.cursor("x abc def"));
+        assert_eq!(Cursor { byte: 3, char: 2 }, Cursor::new("föö", 4));
+        assert_eq!(Cursor { byte: 5, char: 3 }, Cursor::new("föö", 100));
+    }
+
+    #[test]
+    fn test_error_messages() {
+        let parser = literal("foo")
+            .then(integer_i32("bar").min(-5).build_exec(|_: (), _| Ok::<(), Infallible>(())))
+            .then(literal("baz").then(boolean("buzz").build_exec(|_: (), _| Ok::<(), Infallible>(()))));
+        let error = |input| parser.execute((), input).finish().unwrap_err().kind.to_string();
+
+        assert_eq!("Unknown literal 'bar', expected foo", error("bar"));
+        assert_eq!("Unknown literal 'foobar', expected foo", error("foobar 5"));
+        assert_eq!("Expected literal foo", error(""));
+        assert_eq!("Integer must not be less than -5, found -7", error("foo -7"));
+        assert_eq!("Invalid bool, expected true or false but found 'yes'", error("foo baz yes"));
+        assert_eq!("Expected bool", error("foo baz "));
+        assert_eq!("Incomplete command", error("foo baz"));
+    }
+
+    #[test]
+    fn test_expected_one_of() {
+        let parser = literal("foo")
+            .then(literal("add").build_exec(|_: ()| Ok::<(), Infallible>(())))
+            .then(literal("remove").build_exec(|_: ()| Ok::<(), Infallible>(())))
+            .then(literal("list").build_exec(|_: ()| Ok::<(), Infallible>(())))
+            .then(integer_i32("bar").build_exec(|_: (), _| Ok::<(), Infallible>(())));
+
+        let error = parser.execute((), "foo clear").finish().unwrap_err();
+        assert_eq!("Expected one of: add, remove, list, <bar>", error.kind.to_string());
+        assert_eq!(
+            vec![
+                Expected::Literal("add".into()),
+                Expected::Literal("remove".into()),
+                Expected::Literal("list".into()),
+                Expected::Argument("bar".into())
+            ],
+            error.expected
+        );
+    }
+
+    #[test]
+    fn test_furthest_error() {
+        let parser = literal("foo")
+            .then(boolean("a").build_exec(|_: (), a| Ok::<i32, Infallible>(a as i32)))
+            .then(boolean("b").then(integer_i32("c").build_propagate(|_: (), (_, c)| Ok::<i32, Infallible>(c))));
 
-        format!("{}: {}{}<--[HERE]", self.kind, prefix, input)
+        assert_eq!(("", 1), parser.execute((), "foo true").unwrap());
+        assert_eq!(("", 5), parser.execute((), "foo true 5").unwrap());
+        let error = parser.execute((), "foo true x").finish().unwrap_err();
+        assert_eq!("Expected integer: foo true <--[HERE]", error.convert("foo true x", 20));
     }
 }
@@ -0,0 +1,194 @@
+use std::fmt::{Display, Formatter};
+
+use crate::parsers::literal::eq_case;
+use crate::{HelpEntry, HelpUsage};
+
+/// Global help listing of many registered commands.
+///
+/// Entries are collected from any [`HelpUsage`] parser and can be restricted
+/// to sources that meet a requirement (e.g. a permission check). Listings are
+/// sorted by name and split into pages of a fixed size.
+pub struct HelpIndex<S> {
+    entries: Vec<IndexEntry<S>>,
+    page_size: usize,
+    case_sensitive: bool,
+}
+
+struct IndexEntry<S> {
+    entry: HelpEntry,
+    requirement: Option<Box<dyn Fn(S) -> bool>>,
+}
+
+/// Single page of a [`HelpIndex`].
+#[derive(Debug, Clone)]
+pub struct HelpPage<'i> {
+    /// Page number, starting at 1.
+    pub page: usize,
+    /// Total amount of pages.
+    pub pages: usize,
+    /// Entries on this page.
+    pub entries: Vec<&'i HelpEntry>,
+}
+
+/// Result of a [`HelpIndex::query`].
+#[derive(Debug, Clone)]
+pub enum HelpResponse<'i> {
+    /// `help` or `help <page>`.
+    Page(HelpPage<'i>),
+    /// `help <command>`.
+    Command(&'i HelpEntry),
+}
+
+impl<S> HelpIndex<S>
+where
+    S: Copy,
+{
+    /// Create a new empty index with `page_size` entries per page.
+    ///
+    /// # Panics
+    /// If `page_size` is 0.
+    pub fn new(page_size: usize) -> Self {
+        assert!(page_size > 0, "page size must be at least 1");
+        HelpIndex {
+            entries: Vec::new(),
+            page_size,
+            case_sensitive: false,
+        }
+    }
+
+    /// Only look up commands with the exact same case, names are matched
+    /// ignoring case by default.
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    /// Add the help entry of `parser`, visible to every source.
+    pub fn register<P: HelpUsage + ?Sized>(&mut self, parser: &P) -> &mut Self { self.register_entry(parser.help(), None) }
+
+    /// Add the help entry of `parser`, only visible to sources for which
+    /// `requirement` returns true.
+    pub fn register_with<P, R>(&mut self, parser: &P, requirement: R) -> &mut Self
+    where
+        P: HelpUsage + ?Sized,
+        R: Fn(S) -> bool + 'static,
+    {
+        self.register_entry(parser.help(), Some(Box::new(requirement)))
+    }
+
+    fn register_entry(&mut self, entry: HelpEntry, requirement: Option<Box<dyn Fn(S) -> bool>>) -> &mut Self {
+        self.entries.push(IndexEntry { entry, requirement });
+        self
+    }
+
+    fn visible(&self, source: S) -> impl Iterator<Item = &HelpEntry> {
+        self.entries
+            .iter()
+            .filter(move |e| e.requirement.as_ref().map_or(true, |r| r(source)))
+            .map(|e| &e.entry)
+    }
+
+    /// All entries visible to `source`, sorted by name.
+    pub fn entries(&self, source: S) -> Vec<&HelpEntry> {
+        let mut entries: Vec<_> = self.visible(source).collect();
+        if self.case_sensitive {
+            entries.sort_by(|a, b| a.name.cmp(&b.name));
+        } else {
+            entries.sort_by_cached_key(|e| e.name.to_lowercase());
+        }
+        entries
+    }
+
+    /// Returns page `page` (starting at 1) of the entries visible to
+    /// `source`, or `None` if that page does not exist.
+    ///
+    /// The first page always exists, even if it is empty.
+    pub fn page(&self, source: S, page: usize) -> Option<HelpPage<'_>> {
+        let entries = self.entries(source);
+        let pages = entries.len().div_ceil(self.page_size).max(1);
+        if page == 0 || page > pages {
+            return None;
+        }
+        let entries = entries.into_iter().skip((page - 1) * self.page_size).take(self.page_size).collect();
+        Some(HelpPage {
+            page,
+            pages,
+            entries,
+        })
+    }
+
+    /// Find the entry visible to `source` with `name`, ignoring case unless
+    /// [`case_sensitive`](HelpIndex::case_sensitive).
+    pub fn lookup(&self, source: S, name: &str) -> Option<&HelpEntry> {
+        self.visible(source).find(|e| eq_case(&e.name, name, self.case_sensitive))
+    }
+
+    /// Answer the arguments of a help command.
+    ///
+    /// An empty `query` returns the first page, a number returns that page
+    /// and anything else is looked up as a command name. Returns `None` if
+    /// the page or command does not exist.
+    pub fn query(&self, source: S, query: &str) -> Option<HelpResponse<'_>> {
+        let query = query.trim();
+        if query.is_empty() {
+            return self.page(source, 1).map(HelpResponse::Page);
+        }
+        match query.parse::<usize>() {
+            Ok(page) => self.page(source, page).map(HelpResponse::Page),
+            Err(_) => self.lookup(source, query).map(HelpResponse::Command),
+        }
+    }
+}
+
+impl Display for HelpPage<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "--- Help ({}/{}) ---", self.page, self.pages)?;
+        for entry in &self.entries {
+            write!(f, "\n{}", entry)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use crate::parsers::help::ThenHelp;
+    use crate::{literal, BuildExecute, HelpIndex, HelpPage, HelpResponse, UsagePrint};
+
+    #[test]
+    fn test_help_index() {
+        let foo = literal("foo")
+            .build_exec(|_: bool| Ok::<_, Infallible>(()))
+            .help("Foo command")
+            .build_exec(|_: bool, _: UsagePrint<_>| Ok::<_, Infallible>(()));
+        let bar = literal("bar")
+            .build_exec(|_: bool| Ok::<_, Infallible>(()))
+            .help("Bar command")
+            .build_exec(|_: bool, _: UsagePrint<_>| Ok::<_, Infallible>(()));
+        let admin = literal("admin")
+            .build_exec(|_: bool| Ok::<_, Infallible>(()))
+            .help("Admin command")
+            .build_exec(|_: bool, _: UsagePrint<_>| Ok::<_, Infallible>(()));
+
+        let mut index = HelpIndex::new(2);
+        index.register(&foo).register(&bar).register_with(&admin, |op: bool| op);
+
+        let names = |page: HelpPage| page.entries.iter().map(|e| e.name.to_string()).collect::<Vec<_>>();
+        assert_eq!(vec!["bar", "foo"], names(index.page(false, 1).unwrap()));
+        assert_eq!(None, index.page(false, 2).map(names));
+        assert_eq!(vec!["admin", "bar"], names(index.page(true, 1).unwrap()));
+        assert_eq!("--- Help (2/2) ---\nfoo: Foo command", index.page(true, 2).unwrap().to_string());
+
+        match index.query(true, "2") {
+            Some(HelpResponse::Page(page)) => assert_eq!((2, vec!["foo".to_owned()]), (page.page, names(page))),
+            _ => panic!("expected page 2"),
+        }
+        match index.query(true, "ADMIN") {
+            Some(HelpResponse::Command(entry)) => assert_eq!("admin: Admin command", entry.to_string()),
+            _ => panic!("expected the admin command"),
+        }
+        assert!(index.query(false, "admin").is_none());
+    }
+}
@@ -91,20 +91,93 @@
 //!
 //! There will be as many syntaxes as action points (`build_exec`
 //! or`build_propagate`) defined. Note that `foo help` is ignored.
+//! Help can also be requested for a subcommand, `foo bar help` only lists the
+//! usages below `bar`. The keyword can be changed with
+//! [`HelpArgument::keyword`](self::parsers::HelpArgument::keyword).
+//!
+//! ### Parsing without executing
+//!
+//! Parsers also implement [`ParseCommand`], which only parses the input and
+//! returns a [`BoundCommand`]. This type owns the parsed arguments and can be
+//! stored and executed later, as many times as needed:
+//! ```no_run
+//! # use brigadier_rs::{literal, integer_i32, BuildExecute, ParseCommand, Then};
+//! # use std::convert::Infallible;
+//! let parser = literal("foo")
+//!     .then(
+//!         integer_i32("bar")
+//!             .build_exec(|ctx: (), bar| {
+//!                 println!("Bar is {}", bar);
+//!                 Ok::<(), Infallible>(())
+//!             })
+//!     );
+//!
+//! let (_, command) = parser.parse_command((), "foo 5").unwrap();
+//! command.execute(()).unwrap();
+//! command.execute(()).unwrap();
+//! ```
+//!
+//! Commands that are executed repeatedly with the same input can be stored in
+//! a [`CommandCache`] so they only get parsed once.
+//!
+//! ### Asynchronous execution
+//!
+//! Every builder has an asynchronous counterpart (`build_async_exec` and
+//! `build_async_propagate`) that accepts closures returning a future. Such
+//! parsers implement [`AsyncExecute`], which parses the input immediately and
+//! returns a [`CommandFuture`] that runs the command logic when awaited:
+//! ```no_run
+//! # use brigadier_rs::{literal, integer_i32, AsyncExecute, BuildAsyncExecute, Then};
+//! # use std::convert::Infallible;
+//! # async fn run() {
+//! let parser = literal("foo")
+//!     .then(
+//!         integer_i32("bar")
+//!             .build_async_exec(|ctx: (), bar| async move {
+//!                 println!("Bar is {}", bar);
+//!                 Ok::<(), Infallible>(())
+//!             })
+//!     );
+//!
+//! let (_, future) = parser.execute_async((), "foo 5").unwrap();
+//! future.await.unwrap();
+//! # }
+//! ```
 
 mod argument;
+mod bound;
+mod cache;
+mod context;
+mod docs;
 mod error;
+mod help_index;
 pub mod parsers;
+mod render;
+mod translation;
+mod tree;
 mod usage;
 
 pub use argument::*;
-pub use error::{CmdErrorKind, CommandError};
+pub use bound::BoundCommand;
+pub use cache::CommandCache;
+pub use context::CommandContext;
+pub use docs::{CommandDocs, DocFormat};
+pub use error::{CmdErrorKind, CommandError, Cursor, Expected, NumberKind, OwnedCommandError};
+pub use help_index::{HelpIndex, HelpPage, HelpResponse};
 pub use parsers::bool::boolean;
+pub use parsers::branches::{AsyncExecuteBranch, AsyncPropagateBranch, ExecuteBranch, PropagateBranch, ThenAll};
+pub use parsers::case::WithCaseSensitivity;
+pub use parsers::choice::{choice, ChoiceList};
+pub use parsers::described::Describe;
+pub use parsers::dynamic::{DefinitionError, DynamicParser, DynamicUsage};
 pub use parsers::help::{HelpEntry, HelpUsage, ThenHelp};
 pub use parsers::literal::literal;
 pub use parsers::number::{
     float_32, float_64, integer_i16, integer_i32, integer_i64, integer_i8, integer_u16, integer_u32, integer_u64, integer_u8,
 };
+pub use parsers::separator::WithSeparator;
+pub use translation::{Translatable, Translator};
+pub use tree::{CommandNode, CommandTree, NodeKind, NodeProperty};
 pub use usage::*;
 
 /// Parser trait combination of `Execute` and `HelpUsage`.
@@ -114,15 +187,42 @@ pub trait CommandParser<S, U>: Execute<S, U> + HelpUsage {}
 
 impl<S, T, U> CommandParser<S, U> for T where T: Execute<S, U> + HelpUsage {}
 
+/// Parser trait combination of `AsyncExecute` and `HelpUsage`.
+///
+/// Asynchronous counterpart of [`CommandParser`].
+pub trait AsyncCommandParser<S, U>: AsyncExecute<S, U> + HelpUsage {}
+
+impl<S, T, U> AsyncCommandParser<S, U> for T where T: AsyncExecute<S, U> + HelpUsage {}
+
 #[cfg(test)]
 mod tests {
     use std::convert::Infallible;
+    use std::future::Future;
+    use std::pin::pin;
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
 
     use nom::Finish;
 
     use crate::parsers::help::ThenHelp;
     use crate::{boolean, integer_i32, literal, BuildExecute, CommandParser, Execute, Then, UsagePrint};
 
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let waker = Waker::from(Arc::new(NoopWaker));
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
     #[test]
     fn test_main() {
         let parser = literal("foo")
@@ -167,7 +267,7 @@ mod tests {
 
         assert_eq!(("", ()), parser.execute(10, "foo").unwrap());
         assert_eq!(
-            "Unknown input: /foo true<--[HERE]",
+            "Incorrect argument for command: /foo true<--[HERE]",
             parser
                 .execute(10, "foo true hahah")
                 .finish()
@@ -175,7 +275,7 @@ mod tests {
                 .convert("/foo true hahah", 10)
         );
         assert_eq!(
-            "number too large to fit in target type: ...8945645620<--[HERE]",
+            "Invalid integer '12345678945645620': ...8945645620<--[HERE]",
             parser
                 .execute(10, "foo 12345678945645620")
                 .finish()
@@ -7,20 +7,34 @@
 //! - i8, u8, i16, u16, i32, u32, i64, u64, f32, f64: [`NumberArgument`]
 
 pub(crate) mod bool;
+pub(crate) mod branches;
+pub(crate) mod case;
+pub(crate) mod choice;
+pub(crate) mod described;
+pub(crate) mod dispatch;
+pub(crate) mod dynamic;
 pub(crate) mod help;
+pub(crate) mod inherit;
 pub(crate) mod literal;
 pub(crate) mod number;
+pub(crate) mod separator;
 pub(crate) mod then;
 
 use std::marker::PhantomData;
 
+pub use branches::Branches;
+pub use choice::{Choice, ChoiceUsage};
+pub use described::Described;
 pub use help::{HelpArgument, HelpExecutor};
 pub use literal::{LiteralArgument, LiteralExecutor};
 pub use number::NumberArgument;
+pub use separator::Separator;
 pub use then::{CommandThen, LiteralThen, LiteralThenExecutor, ThenExecutor, ThenWrapper};
 
 pub use self::bool::BoolArgument;
-use crate::{ChildUsage, IntoMultipleUsage};
+use crate::tree::executable_nodes;
+use crate::usage::smart::LeafUsage;
+use crate::{ChildUsage, CommandNode, CommandTree, IntoMultipleUsage};
 
 /// Default executor for command argument parsers.
 ///
@@ -49,3 +63,12 @@ where
 
     fn usage_child(&self) -> Self::Child { self.argument.usage_child() }
 }
+
+impl<S, A, C, O> LeafUsage for DefaultExecutor<A, C, O, S> where A: ChildUsage {}
+
+impl<A, C, O, S> CommandTree for DefaultExecutor<A, C, O, S>
+where
+    A: CommandTree,
+{
+    fn command_nodes(&self) -> Vec<CommandNode> { executable_nodes(&self.argument) }
+}
@@ -1,43 +1,68 @@
 use std::marker::PhantomData;
 
-use nom::branch::alt;
-use nom::bytes::complete::tag_no_case;
+use nom::error::{ErrorKind, FromExternalError};
 
+use super::inherit::Inherited;
+use super::literal::eq_case;
 use super::CommandThen;
-use crate::{ArgumentMarkerDefaultImpl, ChildUsage, CommandArgument, CommandError, IntoMultipleUsage, Then};
+use crate::error::{next_token, Expected};
+use crate::usage::smart::LeafUsage;
+use crate::{
+    ArgumentMarkerDefaultImpl, ArgumentUsage, ChildUsage, CmdErrorKind, CommandArgument, CommandError, CommandNode, CommandTree,
+    IntoMultipleUsage, Then,
+};
 
 /// Create a boolean parser
 pub fn boolean<S>(name: &'static str) -> BoolArgument<S> {
     BoolArgument {
         name,
+        case_sensitive: false,
         source: PhantomData,
     }
 }
 
 /// Boolean argument parser.
 ///
-/// This parser has no fields because it simply parses either `"true"` or
-/// `"false`".
+/// This parser simply parses either `"true"` or `"false"`, see
+/// [`case_sensitive`](BoolArgument::case_sensitive).
 pub struct BoolArgument<S> {
     name: &'static str,
+    pub(crate) case_sensitive: bool,
     source: PhantomData<S>,
 }
 
+impl<S> BoolArgument<S> {
+    /// Only accept lowercase `true` and `false` like vanilla, case is ignored
+    /// by default.
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+}
+
 impl<S> CommandArgument<S, bool> for BoolArgument<S> {
     fn parse<'a>(&self, _source: S, input: &'a str) -> nom::IResult<&'a str, bool, CommandError<'a>> {
-        alt((
-            |i| {
-                let (i, _) = tag_no_case("true")(i)?;
-                Ok((i, true))
-            },
-            |i| {
-                let (i, _) = tag_no_case("false")(i)?;
-                Ok((i, false))
-            },
-        ))(input)
+        let token = next_token(input);
+        let case_sensitive = Inherited::case_sensitive(self.case_sensitive);
+        let value = if eq_case(token, "true", case_sensitive) {
+            true
+        } else if eq_case(token, "false", case_sensitive) {
+            false
+        } else {
+            let kind = match token {
+                "" => CmdErrorKind::ExpectedBool,
+                token => CmdErrorKind::InvalidBool(token.to_owned()),
+            };
+            return Err(nom::Err::Error(
+                CommandError::from_external_error(input, ErrorKind::Tag, kind).with_expected(Expected::Argument(self.name.into())),
+            ));
+        };
+        Ok((&input[token.len()..], value))
     }
 }
 
+impl<S> LeafUsage for BoolArgument<S> {}
+
 impl<S> ArgumentMarkerDefaultImpl for BoolArgument<S> {}
 
 impl<S, E> Then<E> for BoolArgument<S> {
@@ -47,6 +72,7 @@ impl<S, E> Then<E> for BoolArgument<S> {
         CommandThen {
             argument: self,
             executor,
+            inherited: Inherited::default(),
             output: PhantomData,
             source: PhantomData,
         }
@@ -54,13 +80,23 @@ impl<S, E> Then<E> for BoolArgument<S> {
 }
 
 impl<S> IntoMultipleUsage for BoolArgument<S> {
-    type Item = <[&'static str; 3] as IntoMultipleUsage>::Item;
+    type Item = <ArgumentUsage as IntoMultipleUsage>::Item;
 
     fn usage_gen(&self) -> Self::Item { self.usage_child().usage_gen() }
 }
 
 impl<S> ChildUsage for BoolArgument<S> {
-    type Child = [&'static str; 3];
+    type Child = ArgumentUsage;
+
+    fn usage_child(&self) -> Self::Child {
+        ArgumentUsage {
+            name: self.name,
+            type_name: "bool",
+            constraint: None,
+        }
+    }
+}
 
-    fn usage_child(&self) -> Self::Child { ["<", self.name, ">"] }
+impl<S> CommandTree for BoolArgument<S> {
+    fn command_nodes(&self) -> Vec<CommandNode> { vec![CommandNode::argument(self.name, "brigadier:bool")] }
 }
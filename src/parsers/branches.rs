@@ -0,0 +1,290 @@
+use std::fmt::{Error, Write};
+
+use nom::IResult;
+
+use super::choice::smart_choice;
+use super::dispatch::LiteralDispatch;
+use super::inherit::Inherited;
+use crate::tree::set_case_sensitive;
+use crate::{
+    AsyncExecute, AsyncPropagate, BoundCommand, BoxedUsages, CommandError, CommandFuture, CommandNode, CommandTree, DynIntoMultipleUsage,
+    DynSmartUsage, Execute, IntoMultipleUsage, ParseCommand, ParsePropagate, Propagate, SmartUsage, Then,
+};
+
+/// Sibling branches collected at runtime.
+///
+/// Like [`Choice`](super::Choice), the first word of the input is looked up
+/// among the literals of all branches and only the matching branches are
+/// tried, branches starting with an argument are tried when no literal branch
+/// matched. When all of them fail the error that got furthest into the input
+/// is returned. Usually this holds trait objects like [`ExecuteBranch`],
+/// [`PropagateBranch`], [`AsyncExecuteBranch`] or [`AsyncPropagateBranch`],
+/// see [`ThenAll`].
+///
+/// A [`Separator`] policy set on the parser these branches are attached to is
+/// inherited by the boxed branches while parsing.
+pub struct Branches<B: ?Sized> {
+    pub(crate) branches: Vec<Box<B>>,
+    pub(crate) inherited: Inherited,
+    dispatch: LiteralDispatch,
+    pub(crate) case_sensitive: bool,
+}
+
+impl<B> Branches<B>
+where
+    B: CommandTree + ?Sized,
+{
+    /// Create a new list of branches.
+    pub fn new(branches: Vec<Box<B>>) -> Self {
+        Branches {
+            dispatch: LiteralDispatch::new(branches.iter().map(|b| b.command_nodes())),
+            case_sensitive: false,
+            branches,
+            inherited: Inherited::default(),
+        }
+    }
+
+    /// Only dispatch to literals with the exact same case as the input, see
+    /// [`Choice::case_sensitive`](super::Choice::case_sensitive).
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    /// Add another branch.
+    pub fn push(&mut self, branch: Box<B>) {
+        self.dispatch.push(branch.command_nodes());
+        self.branches.push(branch);
+    }
+}
+
+impl<B: ?Sized> Branches<B> {
+    fn try_each<'s, 'a, O, F>(&'s self, input: &'a str, mut f: F) -> IResult<&'a str, O, CommandError<'a>>
+    where
+        F: FnMut(&'s B, &'a str) -> IResult<&'a str, O, CommandError<'a>>,
+    {
+        self.inherited.scope(|| {
+            self.dispatch
+                .dispatch(input, Inherited::case_sensitive(self.case_sensitive), |n, i| f(&self.branches[n], i))
+        })
+    }
+}
+
+/// Object safe branch that can be executed or parsed, see [`Branches`].
+pub trait ExecuteBranch<S, U>: Execute<S, U> + ParseCommand<S, U> + DynIntoMultipleUsage + DynSmartUsage + CommandTree {}
+
+impl<T, S, U> ExecuteBranch<S, U> for T where T: Execute<S, U> + ParseCommand<S, U> + DynIntoMultipleUsage + DynSmartUsage + CommandTree {}
+
+/// Object safe branch that can receive propagated data, see [`Branches`].
+pub trait PropagateBranch<S, T, U>:
+    Propagate<S, T, U> + ParsePropagate<S, T, U> + DynIntoMultipleUsage + DynSmartUsage + CommandTree
+{
+}
+
+impl<P, S, T, U> PropagateBranch<S, T, U> for P where
+    P: Propagate<S, T, U> + ParsePropagate<S, T, U> + DynIntoMultipleUsage + DynSmartUsage + CommandTree
+{
+}
+
+/// Object safe branch that can be executed asynchronously, see [`Branches`].
+pub trait AsyncExecuteBranch<S, U>: AsyncExecute<S, U> + DynIntoMultipleUsage + DynSmartUsage + CommandTree {}
+
+impl<T, S, U> AsyncExecuteBranch<S, U> for T where T: AsyncExecute<S, U> + DynIntoMultipleUsage + DynSmartUsage + CommandTree {}
+
+/// Object safe branch that can receive propagated data asynchronously, see
+/// [`Branches`].
+pub trait AsyncPropagateBranch<S, T, U>: AsyncPropagate<S, T, U> + DynIntoMultipleUsage + DynSmartUsage + CommandTree {}
+
+impl<P, S, T, U> AsyncPropagateBranch<S, T, U> for P where P: AsyncPropagate<S, T, U> + DynIntoMultipleUsage + DynSmartUsage + CommandTree {}
+
+/// Attach a list of branches at once, e.g. boxed branches provided by plugins:
+/// ```
+/// # use std::convert::Infallible;
+/// # use brigadier_rs::*;
+/// let branches: Vec<Box<dyn ExecuteBranch<(), i32>>> = vec![
+///     Box::new(literal("a").build_exec(|_| Ok::<_, Infallible>(1))),
+///     Box::new(literal("b").build_exec(|_| Ok::<_, Infallible>(2))),
+/// ];
+/// let parser = literal("plugin").then_all(branches);
+/// assert_eq!(2, parser.execute((), "plugin b").unwrap().1);
+/// ```
+pub trait ThenAll<B: CommandTree + ?Sized>: Then<Branches<B>> {
+    /// Returns the branching parser initialized with `self` and all
+    /// `branches`.
+    fn then_all(self, branches: Vec<Box<B>>) -> Self::Output
+    where
+        Self: Sized,
+    {
+        self.then(Branches::new(branches))
+    }
+}
+
+impl<T, B> ThenAll<B> for T
+where
+    T: Then<Branches<B>>,
+    B: CommandTree + ?Sized,
+{
+}
+
+impl<B, S, U> Execute<S, U> for Branches<B>
+where
+    B: Execute<S, U> + ?Sized,
+    S: Copy,
+{
+    fn execute<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, U, CommandError<'a>> {
+        self.try_each(input, |b, i| b.execute(source, i))
+    }
+}
+
+impl<B, S, T, U> Propagate<S, T, U> for Branches<B>
+where
+    B: Propagate<S, T, U> + ?Sized,
+    S: Copy,
+    T: Copy,
+{
+    fn propagate<'a>(&self, source: S, input: &'a str, data: T) -> IResult<&'a str, U, CommandError<'a>> {
+        self.try_each(input, |b, i| b.propagate(source, i, data))
+    }
+}
+
+impl<B, S, U> ParseCommand<S, U> for Branches<B>
+where
+    B: ParseCommand<S, U> + ?Sized,
+    S: Copy,
+{
+    fn parse_command<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, BoundCommand<'_, S, U>, CommandError<'a>> {
+        self.try_each(input, |b, i| b.parse_command(source, i))
+    }
+}
+
+impl<B, S, T, U> ParsePropagate<S, T, U> for Branches<B>
+where
+    B: ParsePropagate<S, T, U> + ?Sized,
+    S: Copy,
+    T: Copy,
+{
+    fn parse_propagate<'a>(&self, source: S, input: &'a str, data: T) -> IResult<&'a str, BoundCommand<'_, S, U>, CommandError<'a>> {
+        self.try_each(input, |b, i| b.parse_propagate(source, i, data))
+    }
+}
+
+impl<B, S, U> AsyncExecute<S, U> for Branches<B>
+where
+    B: AsyncExecute<S, U> + ?Sized,
+    S: Copy,
+{
+    fn execute_async<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, CommandFuture<U>, CommandError<'a>> {
+        self.try_each(input, |b, i| b.execute_async(source, i))
+    }
+}
+
+impl<B, S, T, U> AsyncPropagate<S, T, U> for Branches<B>
+where
+    B: AsyncPropagate<S, T, U> + ?Sized,
+    S: Copy,
+    T: Copy,
+{
+    fn propagate_async<'a>(&self, source: S, input: &'a str, data: T) -> IResult<&'a str, CommandFuture<U>, CommandError<'a>> {
+        self.try_each(input, |b, i| b.propagate_async(source, i, data))
+    }
+}
+
+impl<B> IntoMultipleUsage for Branches<B>
+where
+    B: DynIntoMultipleUsage + ?Sized,
+{
+    type Item = BoxedUsages;
+
+    fn usage_gen(&self) -> Self::Item {
+        BoxedUsages {
+            usages: self.branches.iter().map(|b| b.dyn_usage_gen()).collect(),
+            index: 0,
+        }
+    }
+}
+
+impl<B> SmartUsage for Branches<B>
+where
+    B: DynSmartUsage + ?Sized,
+{
+    fn node_count(&self) -> usize { self.branches.iter().map(|b| b.dyn_node_count()).sum() }
+
+    fn node_usage<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        for (n, branch) in self.branches.iter().enumerate() {
+            if n > 0 {
+                writer.write_char('|')?;
+            }
+            branch.dyn_node_usage(writer)?;
+        }
+        Ok(())
+    }
+
+    fn smart_usage<W: Write>(&self, writer: &mut W, optional: bool, _depth: usize) -> Result<(), Error> {
+        smart_choice(writer, optional, |w| self.node_usage(w))
+    }
+}
+
+impl<B> CommandTree for Branches<B>
+where
+    B: CommandTree + ?Sized,
+{
+    fn command_nodes(&self) -> Vec<CommandNode> {
+        let mut nodes: Vec<_> = self.branches.iter().flat_map(|b| b.command_nodes()).collect();
+        if let Some(case_sensitive) = self.inherited.case_sensitive {
+            set_case_sensitive(&mut nodes, case_sensitive);
+        }
+        nodes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use nom::Finish;
+
+    use crate::tests::block_on;
+    use crate::{
+        boolean, integer_i32, literal, AsyncExecute, AsyncExecuteBranch, BuildAsyncExecute, BuildExecute, CommandNode, CommandTree,
+        DynamicParser, Execute, ExecuteBranch, IntoMultipleUsage, MultipleUsage, ParseCommand, SmartUsage, Then, ThenAll,
+    };
+
+    #[test]
+    fn test_then_all() {
+        let mut branches: Vec<Box<dyn ExecuteBranch<(), i32>>> = vec![
+            Box::new(literal("enable").build_exec(|_| Ok::<_, Infallible>(1))),
+            Box::new(integer_i32("id").build_exec(|_, id| Ok::<_, Infallible>(id))),
+        ];
+        branches.push(Box::new(literal("disable").then(boolean("force").build_exec(|_, _| Ok::<_, Infallible>(-1)))));
+        let parser = literal("plugin").then_all(branches);
+
+        assert_eq!(1, parser.execute((), "plugin enable").unwrap().1);
+        assert_eq!(42, parser.execute((), "plugin 42").unwrap().1);
+        assert_eq!(-1, parser.execute((), "plugin disable true").unwrap().1);
+        let error = parser.execute((), "plugin disable x").finish().unwrap_err();
+        assert_eq!("x", error.input);
+
+        let usages: Vec<_> = parser.usage_gen().string_iter().map(Result::unwrap).collect();
+        assert_eq!(usages, ["plugin enable", "plugin <id>", "plugin disable <force>"]);
+        assert_eq!(3, parser.command_nodes()[0].children.len());
+        assert_eq!("plugin (enable|<id>|disable)", parser.smart_usage_string(1));
+        let (_, command) = parser.parse_command((), "plugin 7").unwrap();
+        assert_eq!(7, command.execute(()).unwrap());
+
+        let mut definition = CommandNode::literal("dynamic");
+        definition.executable = true;
+        let branches: Vec<Box<dyn ExecuteBranch<(), i32>>> = vec![
+            Box::new(literal("enable").build_exec(|_| Ok::<_, Infallible>(1))),
+            Box::new(DynamicParser::new(definition, |_, _| Ok::<_, Infallible>(2)).unwrap()),
+        ];
+        let parser = literal("plugin").then_all(branches);
+        assert_eq!(2, parser.execute((), "plugin dynamic").unwrap().1);
+
+        let branches: Vec<Box<dyn AsyncExecuteBranch<(), i32>>> = vec![
+            Box::new(literal("enable").build_async_exec(|_| async { Ok::<_, Infallible>(1) })),
+            Box::new(integer_i32("id").build_async_exec(|_, id| async move { Ok::<_, Infallible>(id) })),
+        ];
+        let parser = literal("plugin").then_all(branches);
+        assert_eq!(3, block_on(parser.execute_async((), "plugin 3").unwrap().1).unwrap());
+    }
+}
@@ -0,0 +1,237 @@
+use super::{
+    BoolArgument, Branches, Choice, CommandThen, DefaultExecutor, Described, HelpArgument, HelpExecutor, LiteralArgument, LiteralExecutor,
+    LiteralThen, LiteralThenExecutor, NumberArgument, ThenExecutor, ThenWrapper,
+};
+
+/// Parser whose literals can be made case sensitive all at once.
+///
+/// Unlike the `case_sensitive` builders of single literals and dispatchers,
+/// this reaches every [`LiteralArgument`], [`BoolArgument`] and
+/// [`HelpArgument`] keyword below the parser, including the ones added with
+/// [`Then::then`](crate::Then::then) afterwards and boxed [`Branches`]. Like
+/// [`WithSeparator`](crate::WithSeparator) the setting is resolved while
+/// parsing, a setting on a parent overrides the ones of its children.
+pub trait WithCaseSensitivity {
+    /// Set the case sensitivity of this parser and all of its children.
+    fn set_case_sensitive_all(&mut self, case_sensitive: bool);
+
+    /// Returns this parser only matching literals with the exact same case if
+    /// `case_sensitive`, or ignoring case for all of them otherwise.
+    fn case_sensitive_all(mut self, case_sensitive: bool) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_case_sensitive_all(case_sensitive);
+        self
+    }
+}
+
+impl<S> WithCaseSensitivity for LiteralArgument<S> {
+    fn set_case_sensitive_all(&mut self, case_sensitive: bool) { self.case_sensitive = case_sensitive; }
+}
+
+impl<S> WithCaseSensitivity for BoolArgument<S> {
+    fn set_case_sensitive_all(&mut self, case_sensitive: bool) { self.case_sensitive = case_sensitive; }
+}
+
+impl<N, S> WithCaseSensitivity for NumberArgument<N, S> {
+    fn set_case_sensitive_all(&mut self, _case_sensitive: bool) {}
+}
+
+impl<A, C, O, S> WithCaseSensitivity for DefaultExecutor<A, C, O, S>
+where
+    A: WithCaseSensitivity,
+{
+    fn set_case_sensitive_all(&mut self, case_sensitive: bool) { self.argument.set_case_sensitive_all(case_sensitive); }
+}
+
+impl<A, C, S> WithCaseSensitivity for LiteralExecutor<A, C, S>
+where
+    A: WithCaseSensitivity,
+{
+    fn set_case_sensitive_all(&mut self, case_sensitive: bool) { self.argument.set_case_sensitive_all(case_sensitive); }
+}
+
+impl<A, E, O, S> WithCaseSensitivity for CommandThen<A, E, O, S>
+where
+    A: WithCaseSensitivity,
+{
+    fn set_case_sensitive_all(&mut self, case_sensitive: bool) {
+        self.argument.set_case_sensitive_all(case_sensitive);
+        self.inherited.case_sensitive = Some(case_sensitive);
+    }
+}
+
+impl<A, E, C, O, S> WithCaseSensitivity for ThenExecutor<A, E, C, O, S>
+where
+    A: WithCaseSensitivity,
+{
+    fn set_case_sensitive_all(&mut self, case_sensitive: bool) { self.argument.set_case_sensitive_all(case_sensitive); }
+}
+
+impl<A, E, S> WithCaseSensitivity for LiteralThen<A, E, S>
+where
+    A: WithCaseSensitivity,
+{
+    fn set_case_sensitive_all(&mut self, case_sensitive: bool) {
+        self.argument.set_case_sensitive_all(case_sensitive);
+        self.inherited.case_sensitive = Some(case_sensitive);
+    }
+}
+
+impl<A, E, C, S> WithCaseSensitivity for LiteralThenExecutor<A, E, C, S>
+where
+    A: WithCaseSensitivity,
+{
+    fn set_case_sensitive_all(&mut self, case_sensitive: bool) { self.argument.set_case_sensitive_all(case_sensitive); }
+}
+
+impl<E1, E2> WithCaseSensitivity for ThenWrapper<E1, E2>
+where
+    E1: WithCaseSensitivity,
+    E2: WithCaseSensitivity,
+{
+    fn set_case_sensitive_all(&mut self, case_sensitive: bool) {
+        self.first.set_case_sensitive_all(case_sensitive);
+        self.second.set_case_sensitive_all(case_sensitive);
+    }
+}
+
+impl<B: ?Sized> WithCaseSensitivity for Branches<B> {
+    fn set_case_sensitive_all(&mut self, case_sensitive: bool) {
+        self.case_sensitive = case_sensitive;
+        self.inherited.case_sensitive = Some(case_sensitive);
+    }
+}
+
+impl<E, const N: usize> WithCaseSensitivity for Choice<[E; N]>
+where
+    E: WithCaseSensitivity,
+{
+    fn set_case_sensitive_all(&mut self, case_sensitive: bool) {
+        self.case_sensitive = case_sensitive;
+        for branch in &mut self.branches {
+            branch.set_case_sensitive_all(case_sensitive);
+        }
+    }
+}
+
+impl<E> WithCaseSensitivity for Described<E>
+where
+    E: WithCaseSensitivity,
+{
+    fn set_case_sensitive_all(&mut self, case_sensitive: bool) { self.parser.set_case_sensitive_all(case_sensitive); }
+}
+
+impl<S, E> WithCaseSensitivity for HelpArgument<S, E>
+where
+    E: WithCaseSensitivity,
+{
+    fn set_case_sensitive_all(&mut self, case_sensitive: bool) {
+        self.case_sensitive = case_sensitive;
+        self.argument.set_case_sensitive_all(case_sensitive);
+    }
+}
+
+impl<S, E, C> WithCaseSensitivity for HelpExecutor<S, E, C>
+where
+    E: WithCaseSensitivity,
+{
+    fn set_case_sensitive_all(&mut self, case_sensitive: bool) { self.help.set_case_sensitive_all(case_sensitive); }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use nom::Finish;
+
+    use crate::parsers::help::ThenHelp;
+    use crate::{
+        boolean, choice, literal, BuildExecute, CommandArgument, CommandContext, CommandNode, CommandTree, DynamicParser, Execute,
+        ExecuteBranch, HelpIndex, Then, ThenAll, UsagePrint, WithCaseSensitivity,
+    };
+
+    #[test]
+    fn test_case_sensitivity() {
+        let parser = literal("foo")
+            .then(literal("Bar").case_sensitive(true).build_exec(|_: ()| Ok::<_, Infallible>(true)))
+            .then(boolean("baz").case_sensitive(true).build_exec(|_: (), baz| Ok::<_, Infallible>(baz)));
+        assert_eq!(("", true), parser.execute((), "FOO Bar").unwrap());
+        assert_eq!("Expected one of: Bar, <baz>", parser.execute((), "foo bar").finish().unwrap_err().kind.to_string());
+        assert_eq!(("", false), parser.execute((), "foo false").unwrap());
+        assert_eq!("Expected one of: Bar, <baz>", parser.execute((), "foo False").finish().unwrap_err().kind.to_string());
+        assert_eq!(("", true), boolean::<()>("baz").parse((), "TRUE").unwrap());
+
+        let parser =
+            choice((literal("a").build_exec(|_: ()| Ok::<_, Infallible>(1)), literal("B").build_exec(|_: ()| Ok::<_, Infallible>(2))));
+        assert_eq!(2, parser.execute((), "b").unwrap().1);
+        let parser = parser.case_sensitive(true);
+        assert_eq!(2, parser.execute((), "B").unwrap().1);
+        let error = parser.execute((), "b").finish().unwrap_err();
+        assert_eq!("Expected one of: a, B", error.kind.to_string());
+
+        let foo = literal("Foo")
+            .build_exec(|_: ()| Ok::<_, Infallible>(()))
+            .help("Foo command")
+            .build_exec(|_: (), _: UsagePrint<_>| Ok::<_, Infallible>(()));
+        let mut index = HelpIndex::new(10);
+        index.register(&foo);
+        assert_eq!(Some("Foo"), index.lookup((), "foo").map(|e| e.name.as_ref()));
+        let bar = literal("bar")
+            .build_exec(|_: ()| Ok::<_, Infallible>(()))
+            .help("Bar command")
+            .build_exec(|_: (), _: UsagePrint<_>| Ok::<_, Infallible>(()));
+        index.register(&bar);
+        let names = |index: &HelpIndex<()>| index.entries(()).iter().map(|e| e.name.to_string()).collect::<Vec<_>>();
+        assert_eq!(vec!["bar", "Foo"], names(&index));
+        let mut index = HelpIndex::new(10).case_sensitive(true);
+        index.register(&foo).register(&bar);
+        assert_eq!(None, index.lookup((), "foo").map(|e| e.name.as_ref()));
+        assert_eq!(Some("Foo"), index.lookup((), "Foo").map(|e| e.name.as_ref()));
+        assert_eq!(vec!["Foo", "bar"], names(&index));
+
+        let help = literal("foo")
+            .then(literal("Bar").case_sensitive(true).build_exec(|_: ()| Ok::<_, Infallible>(false)))
+            .help("Foo command")
+            .case_sensitive(true)
+            .build_exec(|_: (), _: UsagePrint<_>| Ok::<_, Infallible>(true));
+        assert_eq!(("", true), help.execute((), "FOO Bar help").unwrap());
+        assert_eq!("Expected one of: help, Bar", help.execute((), "foo bar help").finish().unwrap_err().kind.to_string());
+        assert_eq!((" HELP", false), help.execute((), "foo Bar HELP").unwrap());
+
+        let mut definition = CommandNode::literal("Give");
+        definition.executable = true;
+        definition.case_sensitive = true;
+        let dynamic = DynamicParser::new(definition, |_: (), _: CommandContext| Ok::<_, Infallible>(())).unwrap();
+        assert_eq!(("", ()), dynamic.execute((), "Give").unwrap());
+        assert_eq!("Unknown literal 'give', expected Give", dynamic.execute((), "give").finish().unwrap_err().kind.to_string());
+
+        let branches: Vec<Box<dyn ExecuteBranch<(), i32>>> = vec![Box::new(literal("Boxed").build_exec(|_| Ok::<_, Infallible>(3)))];
+        let parser = literal("foo")
+            .then(literal("Bar").build_exec(|_| Ok::<_, Infallible>(1)))
+            .then(boolean("baz").build_exec(|_, baz| Ok::<_, Infallible>(baz as i32)))
+            .case_sensitive_all(true)
+            .then(literal("Later").build_exec(|_| Ok::<_, Infallible>(2)))
+            .then_all(branches);
+        assert_eq!(1, parser.execute((), "foo Bar").unwrap().1);
+        let error = |input| parser.execute((), input).finish().unwrap_err().kind.to_string();
+        assert_eq!("Unknown literal 'FOO', expected foo", error("FOO Bar"));
+        assert_eq!("Expected one of: Bar, <baz>, Later, Boxed", error("foo True"));
+        assert_eq!("Expected one of: Bar, <baz>, Later, Boxed", error("foo later"));
+        assert_eq!("Expected one of: Bar, <baz>, Later, Boxed", error("foo boxed"));
+        assert_eq!(3, parser.execute((), "foo Boxed").unwrap().1);
+        assert!(parser.command_nodes()[0]
+            .children
+            .iter()
+            .all(|n| n.case_sensitive || n.name == "baz"));
+
+        let parser = literal("foo")
+            .then(literal("Bar").case_sensitive(true).build_exec(|_: ()| Ok::<_, Infallible>(1)))
+            .help("Foo command")
+            .case_sensitive_all(false)
+            .build_exec(|_: (), _: UsagePrint<_>| Ok::<_, Infallible>(2));
+        assert_eq!(1, parser.execute((), "foo bar").unwrap().1);
+        assert_eq!(2, parser.execute((), "FOO BAR HELP").unwrap().1);
+    }
+}
@@ -0,0 +1,489 @@
+use std::fmt::{Error, Write};
+
+use nom::IResult;
+
+use super::dispatch::LiteralDispatch;
+use super::inherit::Inherited;
+use super::Separator;
+use crate::{
+    AsyncExecute, AsyncPropagate, BoundCommand, CommandError, CommandFuture, CommandNode, CommandTree, Execute, IntoMultipleUsage,
+    MultipleUsage, ParseCommand, ParsePropagate, Propagate, SmartUsage, WithCaseSensitivity, WithSeparator,
+};
+
+/// Create a choice between sibling branches.
+///
+/// `branches` is a tuple (of up to 12 branches) or an array, see [`Choice`].
+pub fn choice<T: ChoiceList>(branches: T) -> Choice<T> {
+    Choice {
+        dispatch: LiteralDispatch::new(branches.branch_nodes()),
+        branches,
+        case_sensitive: false,
+    }
+}
+
+/// Parser that chooses between many sibling branches at once.
+///
+/// Instead of trying every branch in turn like
+/// [`ThenWrapper`](super::ThenWrapper), the first word of the input is looked
+/// up in a case-insensitive trie of the literals of all branches, built once
+/// when the choice is created. Only the branches starting with that literal
+/// are tried, branches starting with an argument are tried when no literal
+/// branch matched.
+/// ```
+/// # use std::convert::Infallible;
+/// # use brigadier_rs::*;
+/// let parser = literal("plugin").then(choice((
+///     literal("enable").build_exec(|_| Ok::<_, Infallible>(1)),
+///     literal("disable").build_exec(|_| Ok::<_, Infallible>(0)),
+///     integer_i32("id").build_exec(|_, id| Ok::<_, Infallible>(id)),
+/// )));
+/// assert_eq!(0, parser.execute((), "plugin disable").unwrap().1);
+/// assert_eq!(7, parser.execute((), "plugin 7").unwrap().1);
+/// ```
+pub struct Choice<T> {
+    pub(crate) branches: T,
+    dispatch: LiteralDispatch,
+    pub(crate) case_sensitive: bool,
+}
+
+/// List of sibling branches that can be used in a [`Choice`].
+///
+/// This is implemented for tuples and arrays of parsers.
+pub trait ChoiceList {
+    /// Command nodes of every branch, in order.
+    fn branch_nodes(&self) -> Vec<Vec<CommandNode>>;
+}
+
+impl<T> Choice<T>
+where
+    T: ChoiceList,
+{
+    /// Only dispatch to literals with the exact same case as the input, the
+    /// first word is matched ignoring case by default.
+    ///
+    /// Literals that are case sensitive themselves only match their exact
+    /// case either way, use
+    /// [`case_sensitive_all`](crate::WithCaseSensitivity::case_sensitive_all)
+    /// to set both at once.
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+}
+
+impl<T> CommandTree for Choice<T>
+where
+    T: ChoiceList,
+{
+    fn command_nodes(&self) -> Vec<CommandNode> { self.branches.branch_nodes().into_iter().flatten().collect() }
+}
+
+/// Usages of all branches of a [`Choice`], one after the other.
+#[derive(Debug, Clone)]
+pub struct ChoiceUsage<T> {
+    usages: T,
+    index: usize,
+}
+
+pub(crate) fn smart_choice<W: Write>(
+    writer: &mut W,
+    optional: bool,
+    node_usage: impl FnOnce(&mut W) -> Result<(), Error>,
+) -> Result<(), Error> {
+    writer.write_char(if optional {
+        '['
+    } else {
+        '('
+    })?;
+    node_usage(writer)?;
+    writer.write_char(if optional {
+        ']'
+    } else {
+        ')'
+    })
+}
+
+macro_rules! choice_tuple {
+    ($($name:ident $idx:tt),+) => {
+        impl<$($name),+> ChoiceList for ($($name,)+)
+        where
+            $($name: CommandTree,)+
+        {
+            fn branch_nodes(&self) -> Vec<Vec<CommandNode>> { vec![$(self.$idx.command_nodes()),+] }
+        }
+
+        impl<$($name,)+ S, U> Execute<S, U> for Choice<($($name,)+)>
+        where
+            $($name: Execute<S, U>,)+
+            S: Copy,
+        {
+            fn execute<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, U, CommandError<'a>> {
+                self.dispatch.dispatch(input, Inherited::case_sensitive(self.case_sensitive), |n, i| match n {
+                    $($idx => self.branches.$idx.execute(source, i),)+
+                    _ => unreachable!(),
+                })
+            }
+        }
+
+        impl<$($name,)+ S, T, U> Propagate<S, T, U> for Choice<($($name,)+)>
+        where
+            $($name: Propagate<S, T, U>,)+
+            S: Copy,
+            T: Copy,
+        {
+            fn propagate<'a>(&self, source: S, input: &'a str, data: T) -> IResult<&'a str, U, CommandError<'a>> {
+                self.dispatch.dispatch(input, Inherited::case_sensitive(self.case_sensitive), |n, i| match n {
+                    $($idx => self.branches.$idx.propagate(source, i, data),)+
+                    _ => unreachable!(),
+                })
+            }
+        }
+
+        impl<$($name,)+ S, U> ParseCommand<S, U> for Choice<($($name,)+)>
+        where
+            $($name: ParseCommand<S, U>,)+
+            S: Copy,
+        {
+            fn parse_command<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, BoundCommand<'_, S, U>, CommandError<'a>> {
+                self.dispatch.dispatch(input, Inherited::case_sensitive(self.case_sensitive), |n, i| match n {
+                    $($idx => self.branches.$idx.parse_command(source, i),)+
+                    _ => unreachable!(),
+                })
+            }
+        }
+
+        impl<$($name,)+ S, T, U> ParsePropagate<S, T, U> for Choice<($($name,)+)>
+        where
+            $($name: ParsePropagate<S, T, U>,)+
+            S: Copy,
+            T: Copy,
+        {
+            fn parse_propagate<'a>(&self, source: S, input: &'a str, data: T) -> IResult<&'a str, BoundCommand<'_, S, U>, CommandError<'a>> {
+                self.dispatch.dispatch(input, Inherited::case_sensitive(self.case_sensitive), |n, i| match n {
+                    $($idx => self.branches.$idx.parse_propagate(source, i, data),)+
+                    _ => unreachable!(),
+                })
+            }
+        }
+
+        impl<$($name,)+ S, U> AsyncExecute<S, U> for Choice<($($name,)+)>
+        where
+            $($name: AsyncExecute<S, U>,)+
+            S: Copy,
+        {
+            fn execute_async<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, CommandFuture<U>, CommandError<'a>> {
+                self.dispatch.dispatch(input, Inherited::case_sensitive(self.case_sensitive), |n, i| match n {
+                    $($idx => self.branches.$idx.execute_async(source, i),)+
+                    _ => unreachable!(),
+                })
+            }
+        }
+
+        impl<$($name,)+ S, T, U> AsyncPropagate<S, T, U> for Choice<($($name,)+)>
+        where
+            $($name: AsyncPropagate<S, T, U>,)+
+            S: Copy,
+            T: Copy,
+        {
+            fn propagate_async<'a>(&self, source: S, input: &'a str, data: T) -> IResult<&'a str, CommandFuture<U>, CommandError<'a>> {
+                self.dispatch.dispatch(input, Inherited::case_sensitive(self.case_sensitive), |n, i| match n {
+                    $($idx => self.branches.$idx.propagate_async(source, i, data),)+
+                    _ => unreachable!(),
+                })
+            }
+        }
+
+        impl<$($name),+> IntoMultipleUsage for Choice<($($name,)+)>
+        where
+            $($name: IntoMultipleUsage,)+
+        {
+            type Item = ChoiceUsage<($($name::Item,)+)>;
+
+            fn usage_gen(&self) -> Self::Item {
+                ChoiceUsage {
+                    usages: ($(self.branches.$idx.usage_gen(),)+),
+                    index: 0,
+                }
+            }
+        }
+
+        impl<$($name),+> ChoiceUsage<($($name,)+)>
+        where
+            $($name: MultipleUsage,)+
+        {
+            fn has_next(&self, n: usize) -> Option<bool> {
+                match n {
+                    $($idx => Some(self.usages.$idx.is_next()),)+
+                    _ => None,
+                }
+            }
+
+            fn next_index(&self) -> Option<usize> {
+                let mut n = self.index;
+                while !self.has_next(n)? {
+                    n += 1;
+                }
+                Some(n)
+            }
+        }
+
+        impl<$($name),+> MultipleUsage for ChoiceUsage<($($name,)+)>
+        where
+            $($name: MultipleUsage,)+
+        {
+            fn usage_next<W: Write>(&mut self, writer: &mut W) -> Option<Result<(), Error>> {
+                self.index = self.next_index()?;
+                match self.index {
+                    $($idx => self.usages.$idx.usage_next(writer),)+
+                    _ => None,
+                }
+            }
+
+            fn usage_next_typed<W: Write>(&mut self, writer: &mut W) -> Option<Result<(), Error>> {
+                self.index = self.next_index()?;
+                match self.index {
+                    $($idx => self.usages.$idx.usage_next_typed(writer),)+
+                    _ => None,
+                }
+            }
+
+            fn is_next(&self) -> bool { self.next_index().is_some() }
+
+            fn usage_description(&self) -> Option<&'static str> {
+                match self.next_index()? {
+                    $($idx => self.usages.$idx.usage_description(),)+
+                    _ => None,
+                }
+            }
+        }
+
+        impl<$($name),+> WithSeparator for Choice<($($name,)+)>
+        where
+            $($name: WithSeparator,)+
+        {
+            fn set_separator(&mut self, separator: Separator) {
+                $(self.branches.$idx.set_separator(separator);)+
+            }
+        }
+
+        impl<$($name),+> WithCaseSensitivity for Choice<($($name,)+)>
+        where
+            $($name: WithCaseSensitivity,)+
+        {
+            fn set_case_sensitive_all(&mut self, case_sensitive: bool) {
+                self.case_sensitive = case_sensitive;
+                $(self.branches.$idx.set_case_sensitive_all(case_sensitive);)+
+            }
+        }
+
+        impl<$($name),+> SmartUsage for Choice<($($name,)+)>
+        where
+            $($name: SmartUsage,)+
+        {
+            fn node_count(&self) -> usize { 0 $(+ self.branches.$idx.node_count())+ }
+
+            fn node_usage<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+                $(
+                    if $idx > 0 {
+                        writer.write_char('|')?;
+                    }
+                    self.branches.$idx.node_usage(writer)?;
+                )+
+                Ok(())
+            }
+
+            fn smart_usage<W: Write>(&self, writer: &mut W, optional: bool, _depth: usize) -> Result<(), Error> {
+                smart_choice(writer, optional, |w| self.node_usage(w))
+            }
+        }
+    };
+}
+
+choice_tuple!(E0 0, E1 1);
+choice_tuple!(E0 0, E1 1, E2 2);
+choice_tuple!(E0 0, E1 1, E2 2, E3 3);
+choice_tuple!(E0 0, E1 1, E2 2, E3 3, E4 4);
+choice_tuple!(E0 0, E1 1, E2 2, E3 3, E4 4, E5 5);
+choice_tuple!(E0 0, E1 1, E2 2, E3 3, E4 4, E5 5, E6 6);
+choice_tuple!(E0 0, E1 1, E2 2, E3 3, E4 4, E5 5, E6 6, E7 7);
+choice_tuple!(E0 0, E1 1, E2 2, E3 3, E4 4, E5 5, E6 6, E7 7, E8 8);
+choice_tuple!(E0 0, E1 1, E2 2, E3 3, E4 4, E5 5, E6 6, E7 7, E8 8, E9 9);
+choice_tuple!(E0 0, E1 1, E2 2, E3 3, E4 4, E5 5, E6 6, E7 7, E8 8, E9 9, E10 10);
+choice_tuple!(E0 0, E1 1, E2 2, E3 3, E4 4, E5 5, E6 6, E7 7, E8 8, E9 9, E10 10, E11 11);
+
+impl<E, const N: usize> ChoiceList for [E; N]
+where
+    E: CommandTree,
+{
+    fn branch_nodes(&self) -> Vec<Vec<CommandNode>> { self.iter().map(CommandTree::command_nodes).collect() }
+}
+
+impl<E, S, U, const N: usize> Execute<S, U> for Choice<[E; N]>
+where
+    E: Execute<S, U>,
+    S: Copy,
+{
+    fn execute<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, U, CommandError<'a>> {
+        self.dispatch
+            .dispatch(input, Inherited::case_sensitive(self.case_sensitive), |n, i| self.branches[n].execute(source, i))
+    }
+}
+
+impl<E, S, T, U, const N: usize> Propagate<S, T, U> for Choice<[E; N]>
+where
+    E: Propagate<S, T, U>,
+    S: Copy,
+    T: Copy,
+{
+    fn propagate<'a>(&self, source: S, input: &'a str, data: T) -> IResult<&'a str, U, CommandError<'a>> {
+        self.dispatch
+            .dispatch(input, Inherited::case_sensitive(self.case_sensitive), |n, i| self.branches[n].propagate(source, i, data))
+    }
+}
+
+impl<E, S, U, const N: usize> ParseCommand<S, U> for Choice<[E; N]>
+where
+    E: ParseCommand<S, U>,
+    S: Copy,
+{
+    fn parse_command<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, BoundCommand<'_, S, U>, CommandError<'a>> {
+        self.dispatch
+            .dispatch(input, Inherited::case_sensitive(self.case_sensitive), |n, i| self.branches[n].parse_command(source, i))
+    }
+}
+
+impl<E, S, T, U, const N: usize> ParsePropagate<S, T, U> for Choice<[E; N]>
+where
+    E: ParsePropagate<S, T, U>,
+    S: Copy,
+    T: Copy,
+{
+    fn parse_propagate<'a>(&self, source: S, input: &'a str, data: T) -> IResult<&'a str, BoundCommand<'_, S, U>, CommandError<'a>> {
+        self.dispatch
+            .dispatch(input, Inherited::case_sensitive(self.case_sensitive), |n, i| self.branches[n].parse_propagate(source, i, data))
+    }
+}
+
+impl<E, S, U, const N: usize> AsyncExecute<S, U> for Choice<[E; N]>
+where
+    E: AsyncExecute<S, U>,
+    S: Copy,
+{
+    fn execute_async<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, CommandFuture<U>, CommandError<'a>> {
+        self.dispatch
+            .dispatch(input, Inherited::case_sensitive(self.case_sensitive), |n, i| self.branches[n].execute_async(source, i))
+    }
+}
+
+impl<E, S, T, U, const N: usize> AsyncPropagate<S, T, U> for Choice<[E; N]>
+where
+    E: AsyncPropagate<S, T, U>,
+    S: Copy,
+    T: Copy,
+{
+    fn propagate_async<'a>(&self, source: S, input: &'a str, data: T) -> IResult<&'a str, CommandFuture<U>, CommandError<'a>> {
+        self.dispatch
+            .dispatch(input, Inherited::case_sensitive(self.case_sensitive), |n, i| self.branches[n].propagate_async(source, i, data))
+    }
+}
+
+impl<E, const N: usize> IntoMultipleUsage for Choice<[E; N]>
+where
+    E: IntoMultipleUsage,
+{
+    type Item = ChoiceUsage<[E::Item; N]>;
+
+    fn usage_gen(&self) -> Self::Item {
+        ChoiceUsage {
+            usages: std::array::from_fn(|i| self.branches[i].usage_gen()),
+            index: 0,
+        }
+    }
+}
+
+impl<U, const N: usize> ChoiceUsage<[U; N]>
+where
+    U: MultipleUsage,
+{
+    fn next_index(&self) -> Option<usize> { (self.index..N).find(|&n| self.usages[n].is_next()) }
+}
+
+impl<U, const N: usize> MultipleUsage for ChoiceUsage<[U; N]>
+where
+    U: MultipleUsage,
+{
+    fn usage_next<W: Write>(&mut self, writer: &mut W) -> Option<Result<(), Error>> {
+        self.index = self.next_index()?;
+        self.usages[self.index].usage_next(writer)
+    }
+
+    fn usage_next_typed<W: Write>(&mut self, writer: &mut W) -> Option<Result<(), Error>> {
+        self.index = self.next_index()?;
+        self.usages[self.index].usage_next_typed(writer)
+    }
+
+    fn is_next(&self) -> bool { self.next_index().is_some() }
+
+    fn usage_description(&self) -> Option<&'static str> { self.usages[self.next_index()?].usage_description() }
+}
+
+impl<E, const N: usize> SmartUsage for Choice<[E; N]>
+where
+    E: SmartUsage,
+{
+    fn node_count(&self) -> usize { self.branches.iter().map(SmartUsage::node_count).sum() }
+
+    fn node_usage<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        for (n, branch) in self.branches.iter().enumerate() {
+            if n > 0 {
+                writer.write_char('|')?;
+            }
+            branch.node_usage(writer)?;
+        }
+        Ok(())
+    }
+
+    fn smart_usage<W: Write>(&self, writer: &mut W, optional: bool, _depth: usize) -> Result<(), Error> {
+        smart_choice(writer, optional, |w| self.node_usage(w))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use nom::Finish;
+
+    use crate::{
+        boolean, choice, integer_i32, literal, BuildExecute, CommandTree, Execute, Expected, IntoMultipleUsage, MultipleUsage, SmartUsage,
+        Then,
+    };
+
+    #[test]
+    fn test_choice() {
+        let parser = literal("plugin").then(choice((
+            literal("enable").build_exec(|_| Ok::<_, Infallible>(1)),
+            literal("disable").then(boolean("force").build_exec(|_, _| Ok::<_, Infallible>(-1))),
+            integer_i32("id").build_exec(|_, id| Ok::<_, Infallible>(id)),
+        )));
+        assert_eq!(1, parser.execute((), "plugin ENABLE").unwrap().1);
+        assert_eq!(-1, parser.execute((), "plugin disable true").unwrap().1);
+        assert_eq!(42, parser.execute((), "plugin 42").unwrap().1);
+
+        let error = parser.execute((), "plugin foo").finish().unwrap_err();
+        assert_eq!("foo", error.token());
+        assert_eq!(error.expected, [
+            Expected::Argument("id".into()),
+            Expected::Literal("enable".into()),
+            Expected::Literal("disable".into())
+        ]);
+
+        let usages: Vec<_> = parser.usage_gen().string_iter().map(Result::unwrap).collect();
+        assert_eq!(usages, ["plugin enable", "plugin disable <force>", "plugin <id>"]);
+        assert_eq!("plugin (enable|disable|<id>)", parser.smart_usage_string(1));
+
+        let parser = choice(["a", "bb"].map(|name| literal(name).build_exec(move |_| Ok::<_, Infallible>(name.len()))));
+        assert_eq!(2, parser.execute((), "bb").unwrap().1);
+        assert_eq!(2, parser.command_nodes().len());
+    }
+}
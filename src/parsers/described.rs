@@ -0,0 +1,231 @@
+use nom::IResult;
+
+use super::{CommandThen, DefaultExecutor, LiteralExecutor, LiteralThen, LiteralThenExecutor, ThenExecutor};
+use crate::{
+    AsyncExecute, AsyncPropagate, BoundCommand, ChildUsage, CommandArgument, CommandError, CommandFuture, CommandNode, CommandTree,
+    DescribedUsage, Execute, IntoMultipleUsage, ParseCommand, ParsePropagate, Propagate, SmartUsage, ThenHelp,
+};
+
+/// Parser wrapper that attaches a description or a requirement to a branch.
+///
+/// Every usage produced by the wrapped parser gets this description, unless a
+/// branch further down has a description of its own. See
+/// [`UsagePrint::with_descriptions`](crate::UsagePrint::with_descriptions).
+/// The requirement is only exported in the [`CommandNode`]s of the branch.
+pub struct Described<E> {
+    pub(crate) parser: E,
+    pub(crate) description: Option<&'static str>,
+    pub(crate) requirement: Option<&'static str>,
+}
+
+impl<E> Described<E> {
+    /// Set the description of this branch.
+    pub fn describe(mut self, description: &'static str) -> Self {
+        self.description = Some(description);
+        self
+    }
+
+    /// Set the requirement (e.g. a permission) needed to use this branch.
+    pub fn requires(mut self, requirement: &'static str) -> Self {
+        self.requirement = Some(requirement);
+        self
+    }
+}
+
+/// Type that can be given a description for its usages or a requirement.
+///
+/// This is implemented on branching parsers and executors.
+pub trait Describe {
+    /// Attach `description` to this branch.
+    fn describe(self, description: &'static str) -> Described<Self>
+    where
+        Self: Sized,
+    {
+        Described {
+            parser: self,
+            description: Some(description),
+            requirement: None,
+        }
+    }
+
+    /// Attach `requirement` (e.g. a permission node) to this branch, see
+    /// [`CommandNode::requirement`].
+    fn requires(self, requirement: &'static str) -> Described<Self>
+    where
+        Self: Sized,
+    {
+        Described {
+            parser: self,
+            description: None,
+            requirement: Some(requirement),
+        }
+    }
+}
+
+impl<E, S, O> CommandArgument<S, O> for Described<E>
+where
+    E: CommandArgument<S, O>,
+{
+    fn parse<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, O, CommandError<'a>> { self.parser.parse(source, input) }
+}
+
+impl<E, S, U> Execute<S, U> for Described<E>
+where
+    E: Execute<S, U>,
+{
+    fn execute<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, U, CommandError<'a>> { self.parser.execute(source, input) }
+}
+
+impl<E, S, T, U> Propagate<S, T, U> for Described<E>
+where
+    E: Propagate<S, T, U>,
+{
+    fn propagate<'a>(&self, source: S, input: &'a str, data: T) -> IResult<&'a str, U, CommandError<'a>> {
+        self.parser.propagate(source, input, data)
+    }
+}
+
+impl<E, S, U> ParseCommand<S, U> for Described<E>
+where
+    E: ParseCommand<S, U>,
+{
+    fn parse_command<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, BoundCommand<'_, S, U>, CommandError<'a>> {
+        self.parser.parse_command(source, input)
+    }
+}
+
+impl<E, S, T, U> ParsePropagate<S, T, U> for Described<E>
+where
+    E: ParsePropagate<S, T, U>,
+{
+    fn parse_propagate<'a>(&self, source: S, input: &'a str, data: T) -> IResult<&'a str, BoundCommand<'_, S, U>, CommandError<'a>> {
+        self.parser.parse_propagate(source, input, data)
+    }
+}
+
+impl<E, S, U> AsyncExecute<S, U> for Described<E>
+where
+    E: AsyncExecute<S, U>,
+{
+    fn execute_async<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, CommandFuture<U>, CommandError<'a>> {
+        self.parser.execute_async(source, input)
+    }
+}
+
+impl<E, S, T, U> AsyncPropagate<S, T, U> for Described<E>
+where
+    E: AsyncPropagate<S, T, U>,
+{
+    fn propagate_async<'a>(&self, source: S, input: &'a str, data: T) -> IResult<&'a str, CommandFuture<U>, CommandError<'a>> {
+        self.parser.propagate_async(source, input, data)
+    }
+}
+
+impl<E> IntoMultipleUsage for Described<E>
+where
+    E: IntoMultipleUsage,
+{
+    type Item = DescribedUsage<E::Item>;
+
+    fn usage_gen(&self) -> Self::Item {
+        DescribedUsage {
+            usage: self.parser.usage_gen(),
+            description: self.description,
+        }
+    }
+}
+
+impl<E> ChildUsage for Described<E>
+where
+    E: ChildUsage,
+{
+    type Child = E::Child;
+
+    fn usage_child(&self) -> Self::Child { self.parser.usage_child() }
+}
+
+impl<E> SmartUsage for Described<E>
+where
+    E: SmartUsage,
+{
+    fn node_count(&self) -> usize { self.parser.node_count() }
+
+    fn node_usage<W: std::fmt::Write>(&self, writer: &mut W) -> Result<(), std::fmt::Error> { self.parser.node_usage(writer) }
+
+    fn smart_usage<W: std::fmt::Write>(&self, writer: &mut W, optional: bool, depth: usize) -> Result<(), std::fmt::Error> {
+        self.parser.smart_usage(writer, optional, depth)
+    }
+}
+
+impl<E, S> ThenHelp<S> for Described<E> where E: ThenHelp<S> {}
+
+impl<A, C, O, S> Describe for DefaultExecutor<A, C, O, S> {}
+
+impl<A, C, S> Describe for LiteralExecutor<A, C, S> {}
+
+impl<A, E, S> Describe for LiteralThen<A, E, S> {}
+
+impl<A, E, C, S> Describe for LiteralThenExecutor<A, E, C, S> {}
+
+impl<A, E, O, S> Describe for CommandThen<A, E, O, S> {}
+
+impl<A, E, C, O, S> Describe for ThenExecutor<A, E, C, O, S> {}
+
+impl<E> CommandTree for Described<E>
+where
+    E: CommandTree,
+{
+    fn command_nodes(&self) -> Vec<CommandNode> {
+        let mut nodes = self.parser.command_nodes();
+        for node in &mut nodes {
+            if let Some(description) = self.description {
+                node.description.get_or_insert(description.into());
+            }
+            if let Some(requirement) = self.requirement {
+                node.requirement.get_or_insert(requirement.into());
+            }
+        }
+        nodes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use crate::parsers::help::ThenHelp;
+    use crate::{integer_i32, literal, BuildExecute, Describe, Execute, Then, UsagePrint};
+
+    #[test]
+    fn test_descriptions() {
+        let parser = literal("foo")
+            .then(
+                literal("add")
+                    .then(integer_i32("bar").build_exec(|_: (), _| Ok::<Vec<(String, Option<&str>)>, Infallible>(vec![])))
+                    .describe("Add a number"),
+            )
+            .then(
+                literal("remove")
+                    .then(
+                        integer_i32("bar")
+                            .build_exec(|_: (), _| Ok::<Vec<(String, Option<&str>)>, Infallible>(vec![]))
+                            .describe("Remove a number"),
+                    )
+                    .then(literal("all").build_exec(|_: ()| Ok::<Vec<(String, Option<&str>)>, Infallible>(vec![]))),
+            )
+            .help("Test description")
+            .build_exec(|_: (), usages: UsagePrint<_>| {
+                Ok::<_, Infallible>(usages.with_descriptions().collect::<Result<Vec<_>, _>>().unwrap())
+            });
+
+        let (_, usages) = parser.execute((), "foo help").unwrap();
+        assert_eq!(
+            vec![
+                ("foo add <bar>".to_string(), Some("Add a number")),
+                ("foo remove <bar>".to_string(), Some("Remove a number")),
+                ("foo remove all".to_string(), None),
+            ],
+            usages
+        );
+    }
+}
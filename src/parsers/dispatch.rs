@@ -0,0 +1,200 @@
+use nom::error::ParseError;
+use nom::IResult;
+
+use crate::error::{next_token, Expected};
+use crate::{CommandError, CommandNode, NodeKind};
+
+/// Prefix trie mapping literals to branch indices.
+#[derive(Debug, Clone)]
+pub(crate) struct LiteralTrie {
+    nodes: Vec<TrieNode>,
+    case_sensitive: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+struct TrieNode {
+    /// Children sorted by character, lowercase unless case sensitive.
+    children: Vec<(char, usize)>,
+    branches: Vec<usize>,
+}
+
+impl LiteralTrie {
+    pub(crate) fn new(case_sensitive: bool) -> Self {
+        LiteralTrie {
+            nodes: vec![TrieNode::default()],
+            case_sensitive,
+        }
+    }
+
+    pub(crate) fn insert(&mut self, literal: &str, branch: usize) {
+        let node = if self.case_sensitive {
+            self.insert_chars(literal.chars())
+        } else {
+            self.insert_chars(literal.chars().flat_map(char::to_lowercase))
+        };
+        if !self.nodes[node].branches.contains(&branch) {
+            self.nodes[node].branches.push(branch);
+        }
+    }
+
+    fn insert_chars(&mut self, chars: impl Iterator<Item = char>) -> usize {
+        let mut node = 0;
+        for c in chars {
+            node = match self.nodes[node].children.binary_search_by_key(&c, |&(c, _)| c) {
+                Ok(i) => self.nodes[node].children[i].1,
+                Err(i) => {
+                    self.nodes.push(TrieNode::default());
+                    let child = self.nodes.len() - 1;
+                    self.nodes[node].children.insert(i, (c, child));
+                    child
+                },
+            };
+        }
+        node
+    }
+
+    /// Branches with a literal equal to `token`, ignoring case unless case
+    /// sensitive.
+    pub(crate) fn get(&self, token: &str) -> &[usize] {
+        let node = if self.case_sensitive {
+            self.find_chars(token.chars())
+        } else {
+            self.find_chars(token.chars().flat_map(char::to_lowercase))
+        };
+        node.map_or(&[], |node| &self.nodes[node].branches)
+    }
+
+    fn find_chars(&self, chars: impl Iterator<Item = char>) -> Option<usize> {
+        let mut node = 0;
+        for c in chars {
+            let i = self.nodes[node].children.binary_search_by_key(&c, |&(c, _)| c).ok()?;
+            node = self.nodes[node].children[i].1;
+        }
+        Some(node)
+    }
+}
+
+/// Literal dispatch table of a list of sibling branches.
+///
+/// The first word of the input is looked up in a trie of the literals of all
+/// branches, only the matching branches are tried. Branches that start with
+/// an argument are tried when no literal branch succeeds.
+///
+/// Whether case is ignored can be inherited from a parent while parsing, so
+/// both tries are built up front.
+#[derive(Debug, Clone)]
+pub(crate) struct LiteralDispatch {
+    literals: LiteralTrie,
+    exact: LiteralTrie,
+    arguments: Vec<usize>,
+    expected: Vec<Expected>,
+    len: usize,
+}
+
+impl LiteralDispatch {
+    /// Build the dispatch table of branches consisting of `nodes`.
+    pub(crate) fn new<I>(nodes: I) -> Self
+    where
+        I: IntoIterator<Item = Vec<CommandNode>>,
+    {
+        let mut dispatch = LiteralDispatch {
+            literals: LiteralTrie::new(false),
+            exact: LiteralTrie::new(true),
+            arguments: Vec::new(),
+            expected: Vec::new(),
+            len: 0,
+        };
+        for nodes in nodes {
+            dispatch.push(nodes);
+        }
+        dispatch
+    }
+
+    /// Add the next branch, consisting of `nodes`.
+    pub(crate) fn push(&mut self, nodes: Vec<CommandNode>) {
+        let n = self.len;
+        self.len += 1;
+        if nodes.is_empty() || nodes.iter().any(|node| !matches!(node.kind, NodeKind::Literal)) {
+            self.arguments.push(n);
+        }
+        for node in nodes.into_iter().filter(|node| matches!(node.kind, NodeKind::Literal)) {
+            self.literals.insert(&node.name, n);
+            self.exact.insert(&node.name, n);
+            let expected = Expected::Literal(node.name);
+            if !self.expected.contains(&expected) {
+                self.expected.push(expected);
+            }
+        }
+    }
+
+    /// Try the branches matching `input`, `f` parses branch `n`. Literals
+    /// only match with the exact same case if `case_sensitive`.
+    ///
+    /// When all of them fail the error that got furthest is returned.
+    pub(crate) fn dispatch<'a, O, F>(&self, input: &'a str, case_sensitive: bool, mut f: F) -> IResult<&'a str, O, CommandError<'a>>
+    where
+        F: FnMut(usize, &'a str) -> IResult<&'a str, O, CommandError<'a>>,
+    {
+        let trie = if case_sensitive {
+            &self.exact
+        } else {
+            &self.literals
+        };
+        let literals = trie.get(next_token(input));
+        let arguments = self.arguments.iter().filter(|n| !literals.contains(n));
+        let mut error: Option<CommandError> = None;
+        for &n in literals.iter().chain(arguments) {
+            match f(n, input) {
+                Err(nom::Err::Error(e)) => {
+                    error = Some(match error {
+                        Some(error) => error.or(e),
+                        None => e,
+                    })
+                },
+                result => return result,
+            }
+        }
+        Err(nom::Err::Error(match error {
+            Some(error) if literals.is_empty() => error.or(self.unknown_literal(input)),
+            Some(error) => error,
+            None => self.unknown_literal(input),
+        }))
+    }
+
+    fn unknown_literal<'a>(&self, input: &'a str) -> CommandError<'a> { CommandError::unknown_literal(input, self.expected.clone()) }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use nom::Finish;
+
+    use crate::{choice, integer_i32, literal, BuildExecute, Execute, ExecuteBranch, Then, ThenAll};
+
+    #[test]
+    fn test_literal_dispatch() {
+        let parser =
+            choice(std::array::from_fn::<_, 100, _>(|n| literal(format!("cmd{}", n)).build_exec(move |_| Ok::<_, Infallible>(n as i32))));
+        assert_eq!(42, parser.execute((), "CMD42").unwrap().1);
+        assert_eq!(4, parser.execute((), "cmd4").unwrap().1);
+        let error = parser.execute((), "cmd100").finish().unwrap_err();
+        assert_eq!("cmd100", error.token());
+        assert_eq!(100, error.expected.len());
+
+        let branches: Vec<Box<dyn ExecuteBranch<(), i32>>> = vec![
+            Box::new(literal("a").then(integer_i32("x").build_exec(|_, x| Ok::<_, Infallible>(x)))),
+            Box::new(literal("add").build_exec(|_| Ok::<_, Infallible>(-1))),
+            Box::new(literal("a").build_exec(|_| Ok::<_, Infallible>(-2))),
+            Box::new(integer_i32("y").build_exec(|_, y| Ok::<_, Infallible>(y * 2))),
+        ];
+        let parser = literal("root").then_all(branches);
+        assert_eq!(3, parser.execute((), "root a 3").unwrap().1);
+        assert_eq!(-2, parser.execute((), "root A").unwrap().1);
+        assert_eq!(-1, parser.execute((), "root add").unwrap().1);
+        assert_eq!(10, parser.execute((), "root 5").unwrap().1);
+        let error = parser.execute((), "root ad").finish().unwrap_err();
+        assert_eq!("ad", error.token());
+        assert_eq!(3, error.expected.len());
+    }
+}
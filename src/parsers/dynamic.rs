@@ -0,0 +1,561 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Error, Write};
+
+use nom::error::{ErrorKind, FromExternalError, ParseError};
+use nom::IResult;
+
+use super::bool::BoolArgument;
+use super::inherit::Inherited;
+use super::literal::literal_word;
+use super::number::NumberArgument;
+use super::separator::separated;
+use super::Separator;
+use crate::argument::box_task;
+use crate::bound::Bound;
+use crate::error::Expected;
+use crate::tree::set_case_sensitive;
+use crate::{
+    boolean, float_32, float_64, integer_i32, integer_i64, AsyncExecute, AsyncTaskLogic, BoundCommand, ChildUsage, CmdErrorKind,
+    CommandArgument, CommandContext, CommandError, CommandFuture, CommandNode, CommandTree, Execute, HelpEntry, HelpUsage,
+    IntoMultipleUsage, NodeKind, NodeProperty, ParseCommand, SingleUsage, SmartUsage, TaskLogic, WithCaseSensitivity, WithSeparator,
+};
+
+/// Type-erased parser built at runtime from a [`CommandNode`] tree.
+///
+/// Literal nodes are matched by name, argument nodes use the built-in
+/// argument parsers (`brigadier:bool`, `brigadier:integer`, `brigadier:long`,
+/// `brigadier:float` and `brigadier:double` with optional `min` and `max`
+/// properties). Every executable node runs the same task with the parsed
+/// arguments in a [`CommandContext`].
+///
+/// With the `serde` feature, the definition can be loaded from any format
+/// serde supports, e.g. JSON:
+/// ```json
+/// {"type": "literal", "name": "foo", "children": [
+///     {"type": "argument", "name": "bar", "parser": "brigadier:integer", "properties": {"min": 0}, "executable": true}
+/// ]}
+/// ```
+pub struct DynamicParser<S, C> {
+    definition: CommandNode,
+    root: DynamicNode<S>,
+    usages: Vec<DynamicUsage>,
+    task: C,
+}
+
+struct DynamicNode<S> {
+    name: Cow<'static, str>,
+    argument: DynamicArgument<S>,
+    executable: bool,
+    case_sensitive: bool,
+    /// Only set on the root, the other nodes inherit it.
+    inherited: Inherited,
+    children: Vec<DynamicNode<S>>,
+}
+
+enum DynamicArgument<S> {
+    Literal,
+    Integer(NumberArgument<i32, S>),
+    Long(NumberArgument<i64, S>),
+    Float(NumberArgument<f32, S>),
+    Double(NumberArgument<f64, S>),
+    Bool(BoolArgument<S>),
+}
+
+/// Invalid [`CommandNode`] definition for a [`DynamicParser`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DefinitionError {
+    /// Argument node has a parser type that is not supported.
+    UnknownParser(Cow<'static, str>),
+    /// Argument node has an unknown property or a value of the wrong type.
+    InvalidProperty {
+        argument: Cow<'static, str>,
+        property: Cow<'static, str>,
+    },
+}
+
+/// Single usage of a [`DynamicParser`].
+#[derive(Debug, Clone)]
+pub struct DynamicUsage {
+    usage: String,
+    typed: String,
+}
+
+impl<S, C> DynamicParser<S, C>
+where
+    S: Copy,
+{
+    /// Build a parser from `definition`, `task` is executed for every
+    /// executable node.
+    pub fn new(definition: CommandNode, task: C) -> Result<Self, DefinitionError> {
+        let root = DynamicNode::compile(&definition)?;
+        let mut usages = Vec::new();
+        root.collect_usages(&mut String::new(), &mut String::new(), &mut usages);
+        Ok(DynamicParser {
+            definition,
+            root,
+            usages,
+            task,
+        })
+    }
+}
+
+impl<S> DynamicNode<S>
+where
+    S: Copy,
+{
+    fn compile(node: &CommandNode) -> Result<Self, DefinitionError> {
+        let argument = match &node.kind {
+            NodeKind::Literal => DynamicArgument::Literal,
+            NodeKind::Argument { parser, properties } => match parser.as_ref() {
+                "brigadier:integer" => DynamicArgument::Integer(bounded(integer_i32(""), node, properties)?),
+                "brigadier:long" => DynamicArgument::Long(bounded(integer_i64(""), node, properties)?),
+                "brigadier:float" => DynamicArgument::Float(bounded(float_32(""), node, properties)?),
+                "brigadier:double" => DynamicArgument::Double(bounded(float_64(""), node, properties)?),
+                "brigadier:bool" => match properties.keys().next() {
+                    None => DynamicArgument::Bool(boolean("")),
+                    Some(property) => return Err(invalid_property(node, property)),
+                },
+                _ => return Err(DefinitionError::UnknownParser(parser.clone())),
+            },
+        };
+        Ok(DynamicNode {
+            name: node.name.clone(),
+            argument,
+            executable: node.executable,
+            case_sensitive: node.case_sensitive,
+            inherited: Inherited::default(),
+            children: node.children.iter().map(Self::compile).collect::<Result<_, _>>()?,
+        })
+    }
+
+    fn collect_usages(&self, usage: &mut String, typed: &mut String, usages: &mut Vec<DynamicUsage>) {
+        let (len, typed_len) = (usage.len(), typed.len());
+        if len > 0 {
+            usage.push(' ');
+            typed.push(' ');
+        }
+        // writing to a String cannot fail
+        let _ = self.write_usage(usage, typed);
+        if self.executable {
+            usages.push(DynamicUsage {
+                usage: usage.clone(),
+                typed: typed.clone(),
+            });
+        }
+        for child in &self.children {
+            child.collect_usages(usage, typed, usages);
+        }
+        usage.truncate(len);
+        typed.truncate(typed_len);
+    }
+
+    fn write_usage(&self, usage: &mut String, typed: &mut String) -> Result<(), Error> {
+        let child = match &self.argument {
+            DynamicArgument::Literal => {
+                usage.push_str(&self.name);
+                typed.push_str(&self.name);
+                return Ok(());
+            },
+            DynamicArgument::Integer(argument) => argument.usage_child(),
+            DynamicArgument::Long(argument) => argument.usage_child(),
+            DynamicArgument::Float(argument) => argument.usage_child(),
+            DynamicArgument::Double(argument) => argument.usage_child(),
+            DynamicArgument::Bool(argument) => argument.usage_child(),
+        };
+        write!(usage, "<{}>", self.name)?;
+        write!(typed, "<{}: {}", self.name, child.type_name)?;
+        if let Some(constraint) = &child.constraint {
+            write!(typed, " {}", constraint)?;
+        }
+        typed.write_char('>')
+    }
+
+    fn parse_argument<'a>(&self, source: S, input: &'a str, ctx: &mut CommandContext) -> IResult<&'a str, (), CommandError<'a>> {
+        let name = self.name.clone();
+        let result = match &self.argument {
+            DynamicArgument::Literal => {
+                let (rest, _) = literal_word(self.name.clone(), self.case_sensitive, input)?;
+                ctx.push_literal(name);
+                return Ok((rest, ()));
+            },
+            DynamicArgument::Integer(argument) => ctx.parse(name, argument, source, input),
+            DynamicArgument::Long(argument) => ctx.parse(name, argument, source, input),
+            DynamicArgument::Float(argument) => ctx.parse(name, argument, source, input),
+            DynamicArgument::Double(argument) => ctx.parse(name, argument, source, input),
+            DynamicArgument::Bool(argument) => ctx.parse(name, argument, source, input),
+        };
+        result.map_err(|e| {
+            e.map(|mut e| {
+                e.expected = vec![Expected::Argument(self.name.clone())];
+                e
+            })
+        })
+    }
+
+    fn set_case_sensitive(&mut self, case_sensitive: bool) {
+        self.case_sensitive = case_sensitive;
+        for child in &mut self.children {
+            child.set_case_sensitive(case_sensitive);
+        }
+    }
+
+    fn parse<'a>(&self, source: S, input: &'a str, ctx: &mut CommandContext) -> IResult<&'a str, (), CommandError<'a>> {
+        let (rest, _) = self.parse_argument(source, input, ctx)?;
+        if rest.is_empty() && self.executable {
+            return Ok((rest, ()));
+        }
+        let children = separated(self.inherited, rest, |rest| parse_children(&self.children, source, rest, ctx));
+        match children {
+            Err(nom::Err::Error(e)) if self.executable => {
+                Err(nom::Err::Error(e.or(CommandError::from_external_error(rest, ErrorKind::IsNot, CmdErrorKind::IncorrectArgument))))
+            },
+            result => result,
+        }
+    }
+}
+
+/// Try every node in order, keeping the error that got furthest.
+fn parse_children<'a, S>(
+    nodes: &[DynamicNode<S>],
+    source: S,
+    input: &'a str,
+    ctx: &mut CommandContext,
+) -> IResult<&'a str, (), CommandError<'a>>
+where
+    S: Copy,
+{
+    let mut error: Option<CommandError> = None;
+    for node in nodes {
+        let mark = ctx.mark();
+        match node.parse(source, input, ctx) {
+            Err(nom::Err::Error(e)) => {
+                ctx.reset(mark);
+                error = Some(match error {
+                    Some(error) => error.or(e),
+                    None => e,
+                });
+            },
+            result => return result,
+        }
+    }
+    Err(nom::Err::Error(
+        error.unwrap_or_else(|| CommandError::from_external_error(input, ErrorKind::IsNot, CmdErrorKind::IncorrectArgument)),
+    ))
+}
+
+fn bounded<N, S>(
+    mut argument: NumberArgument<N, S>,
+    node: &CommandNode,
+    properties: &BTreeMap<Cow<'static, str>, NodeProperty>,
+) -> Result<NumberArgument<N, S>, DefinitionError>
+where
+    N: FromProperty + PartialOrd,
+{
+    let (mut min, mut max) = (None, None);
+    for (key, value) in properties {
+        let value = N::from_property(*value).ok_or_else(|| invalid_property(node, key))?;
+        match key.as_ref() {
+            "min" => min = Some(value),
+            "max" => max = Some(value),
+            _ => return Err(invalid_property(node, key)),
+        }
+    }
+    if let (Some(min), Some(max)) = (&min, &max) {
+        if min > max {
+            return Err(invalid_property(node, "max"));
+        }
+    }
+    if let Some(min) = min {
+        argument = argument.min(min);
+    }
+    if let Some(max) = max {
+        argument = argument.max(max);
+    }
+    Ok(argument)
+}
+
+fn invalid_property(node: &CommandNode, property: &str) -> DefinitionError {
+    DefinitionError::InvalidProperty {
+        argument: node.name.clone(),
+        property: property.to_owned().into(),
+    }
+}
+
+/// Number that can be read from a [`NodeProperty`] without losing precision.
+trait FromProperty: Sized {
+    fn from_property(property: NodeProperty) -> Option<Self>;
+}
+
+macro_rules! impl_from_property {
+    ($($ty:ty),* => integer) => {
+        $(impl FromProperty for $ty {
+            fn from_property(property: NodeProperty) -> Option<Self> {
+                match property {
+                    NodeProperty::Integer(v) => v.try_into().ok(),
+                    NodeProperty::Unsigned(v) => v.try_into().ok(),
+                    NodeProperty::Float(_) => None,
+                }
+            }
+        })*
+    };
+    ($($ty:ty),* => float) => {
+        $(impl FromProperty for $ty {
+            fn from_property(property: NodeProperty) -> Option<Self> {
+                let value = match property {
+                    NodeProperty::Integer(v) => Some(v as $ty).filter(|f| *f as i128 == v as i128),
+                    NodeProperty::Unsigned(v) => Some(v as $ty).filter(|f| *f as u128 == v as u128),
+                    NodeProperty::Float(v) => Some(v as $ty).filter(|f| *f as f64 == v),
+                }?;
+                value.is_finite().then_some(value)
+            }
+        })*
+    };
+}
+
+impl_from_property!(i32, i64 => integer);
+impl_from_property!(f32, f64 => float);
+
+impl<S, C> CommandArgument<S, CommandContext> for DynamicParser<S, C>
+where
+    S: Copy,
+{
+    fn parse<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, CommandContext, CommandError<'a>> {
+        let mut ctx = CommandContext::new();
+        let (input, _) = self.root.parse(source, input, &mut ctx)?;
+        Ok((input, ctx))
+    }
+}
+
+impl<S, C, U> Execute<S, U> for DynamicParser<S, C>
+where
+    C: TaskLogic<S, CommandContext, Output = U>,
+    S: Copy,
+{
+    fn execute<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, U, CommandError<'a>> {
+        let (input, args) = self.parse(source, input)?;
+        match self.task.run(source, args) {
+            Err(e) => Err(nom::Err::Failure(CommandError::from_external_error(input, ErrorKind::MapRes, e))),
+            Ok(v) => Ok((input, v)),
+        }
+    }
+}
+
+impl<S, C, U> ParseCommand<S, U> for DynamicParser<S, C>
+where
+    C: TaskLogic<S, CommandContext, Output = U>,
+    S: Copy,
+{
+    fn parse_command<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, BoundCommand<'_, S, U>, CommandError<'a>> {
+        let (input, args) = self.parse(source, input)?;
+        Ok((
+            input,
+            BoundCommand::new(Bound {
+                task: &self.task,
+                args,
+            }),
+        ))
+    }
+}
+
+impl<S, C, U> AsyncExecute<S, U> for DynamicParser<S, C>
+where
+    C: AsyncTaskLogic<S, CommandContext, Output = U>,
+    S: Copy,
+{
+    fn execute_async<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, CommandFuture<U>, CommandError<'a>> {
+        let (input, args) = self.parse(source, input)?;
+        Ok((input, box_task(self.task.run(source, args))))
+    }
+}
+
+impl<S, C> HelpUsage for DynamicParser<S, C> {
+    fn help(&self) -> HelpEntry {
+        HelpEntry {
+            name: self.definition.name.clone(),
+            description: self.definition.description.clone().unwrap_or_default(),
+        }
+    }
+}
+
+impl<S, C> IntoMultipleUsage for DynamicParser<S, C> {
+    type Item = std::vec::IntoIter<DynamicUsage>;
+
+    fn usage_gen(&self) -> Self::Item { self.usages.clone().into_iter() }
+}
+
+impl<S, C> WithCaseSensitivity for DynamicParser<S, C>
+where
+    S: Copy,
+{
+    fn set_case_sensitive_all(&mut self, case_sensitive: bool) {
+        self.root.set_case_sensitive(case_sensitive);
+        set_case_sensitive(std::slice::from_mut(&mut self.definition), case_sensitive);
+    }
+}
+
+impl<S, C> WithSeparator for DynamicParser<S, C>
+where
+    S: Copy,
+{
+    fn set_separator(&mut self, separator: Separator) { self.root.inherited.separator = Some(separator); }
+}
+
+impl<S> DynamicNode<S> {
+    fn node_usage<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        match self.argument {
+            DynamicArgument::Literal => writer.write_str(&self.name),
+            _ => write!(writer, "<{}>", self.name),
+        }
+    }
+
+    fn smart_usage<W: Write>(&self, writer: &mut W, optional: bool, depth: usize) -> Result<(), Error> {
+        if optional {
+            writer.write_char('[')?;
+        }
+        self.node_usage(writer)?;
+        if depth > 0 {
+            match self.children.as_slice() {
+                [] => {},
+                [child] => {
+                    writer.write_char(' ')?;
+                    child.smart_usage(writer, self.executable, depth - 1)?;
+                },
+                children => {
+                    writer.write_str(if self.executable {
+                        " ["
+                    } else {
+                        " ("
+                    })?;
+                    for (n, child) in children.iter().enumerate() {
+                        if n > 0 {
+                            writer.write_char('|')?;
+                        }
+                        child.node_usage(writer)?;
+                    }
+                    writer.write_char(if self.executable {
+                        ']'
+                    } else {
+                        ')'
+                    })?;
+                },
+            }
+        }
+        if optional {
+            writer.write_char(']')?;
+        }
+        Ok(())
+    }
+}
+
+impl<S, C> SmartUsage for DynamicParser<S, C> {
+    fn node_count(&self) -> usize { 1 }
+
+    fn node_usage<W: Write>(&self, writer: &mut W) -> Result<(), Error> { self.root.node_usage(writer) }
+
+    fn smart_usage<W: Write>(&self, writer: &mut W, optional: bool, depth: usize) -> Result<(), Error> {
+        self.root.smart_usage(writer, optional, depth)
+    }
+}
+
+impl<S, C> CommandTree for DynamicParser<S, C> {
+    fn command_nodes(&self) -> Vec<CommandNode> { vec![self.definition.clone()] }
+}
+
+impl SingleUsage for DynamicUsage {
+    fn usage<W: Write>(&self, writer: &mut W) -> Result<(), Error> { writer.write_str(&self.usage) }
+
+    fn usage_typed<W: Write>(&self, writer: &mut W) -> Result<(), Error> { writer.write_str(&self.typed) }
+}
+
+impl Display for DefinitionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DefinitionError::UnknownParser(parser) => write!(f, "Unknown argument parser {}", parser),
+            DefinitionError::InvalidProperty { argument, property } => write!(f, "Invalid property {} for argument {}", property, argument),
+        }
+    }
+}
+
+impl std::error::Error for DefinitionError {}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use crate::parsers::help::ThenHelp;
+    use crate::{
+        literal, BuildExecute, CommandContext, CommandNode, CommandParser, DefinitionError, DynamicParser, IntoMultipleUsage,
+        MultipleUsage, SmartUsage, UsagePrint,
+    };
+
+    #[test]
+    fn test_dynamic_parser() {
+        let mut amount = CommandNode::argument("amount", "brigadier:integer").with_property("min", 1);
+        amount.executable = true;
+        let mut all = CommandNode::literal("all");
+        all.executable = true;
+        let mut definition = CommandNode::literal("give");
+        definition.children = vec![amount, all];
+
+        let dynamic = DynamicParser::new(definition, |_: (), ctx: CommandContext| match ctx.get::<i32>("amount") {
+            Some(amount) => Ok::<_, Infallible>(*amount),
+            _ => Ok(-1),
+        })
+        .unwrap();
+        assert_eq!(
+            vec!["give <amount: i32 1..>", "give all"],
+            dynamic.usage_gen().string_iter().typed().collect::<Result<Vec<_>, _>>().unwrap()
+        );
+        assert_eq!("give (<amount>|all)", dynamic.smart_usage_string(1));
+        let typed = literal("foo")
+            .build_exec(|_: ()| Ok::<_, Infallible>(0))
+            .help("Foo command")
+            .build_exec(|_: (), _: UsagePrint<_>| Ok::<_, Infallible>(0));
+        let parsers: Vec<Box<dyn CommandParser<(), i32>>> = vec![Box::new(typed), Box::new(dynamic)];
+
+        let execute = |input| parsers.iter().find_map(|p| p.execute((), input).ok()).map(|(_, v)| v);
+        assert_eq!(Some(0), execute("foo"));
+        assert_eq!(Some(5), execute("give 5"));
+        assert_eq!(Some(-1), execute("give all"));
+        assert_eq!(None, execute("give 0"));
+        assert_eq!(None, execute("give"));
+
+        let dynamic = DynamicParser::<(), _>::new(CommandNode::argument("bar", "brigadier:string"), |_: (), _: CommandContext| {
+            Ok::<_, Infallible>(())
+        });
+        assert_eq!(Some(DefinitionError::UnknownParser("brigadier:string".into())), dynamic.err());
+        let invalid = |node: CommandNode| DynamicParser::<(), _>::new(node, |_: (), _: CommandContext| Ok::<_, Infallible>(())).err();
+        let invalid_property = |property: &'static str| DefinitionError::InvalidProperty {
+            argument: "bar".into(),
+            property: property.into(),
+        };
+        let inverted = CommandNode::argument("bar", "brigadier:integer")
+            .with_property("min", 10)
+            .with_property("max", 5);
+        assert_eq!(Some(invalid_property("max")), invalid(inverted));
+        let too_big = CommandNode::argument("bar", "brigadier:integer").with_property("max", u64::MAX);
+        assert_eq!(Some(invalid_property("max")), invalid(too_big));
+        let imprecise = CommandNode::argument("bar", "brigadier:float").with_property("max", 16_777_217);
+        assert_eq!(Some(invalid_property("max")), invalid(imprecise));
+        let exact = CommandNode::argument("bar", "brigadier:double")
+            .with_property("min", -2)
+            .with_property("max", 0.5);
+        assert_eq!(None, invalid(exact));
+
+        #[cfg(feature = "serde")]
+        {
+            use crate::Execute;
+
+            let definition = serde_json::from_str(
+                r#"{"type": "literal", "name": "foo", "children": [
+                    {"type": "argument", "name": "bar", "parser": "brigadier:integer", "properties": {"max": 10}, "executable": true}
+                ]}"#,
+            )
+            .unwrap();
+            let dynamic =
+                DynamicParser::new(definition, |_: (), ctx: CommandContext| Ok::<_, Infallible>(ctx.get::<i32>("bar").copied())).unwrap();
+            assert_eq!(Some(7), dynamic.execute((), "foo 7").unwrap().1);
+        }
+    }
+}
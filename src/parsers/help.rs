@@ -2,23 +2,47 @@ use std::borrow::Cow;
 use std::marker::PhantomData;
 
 use nom::branch::alt;
-use nom::bytes::complete::tag_no_case;
-use nom::character::complete::char;
 use nom::error::{ErrorKind, FromExternalError};
 
-use super::{LiteralExecutor, LiteralThen, LiteralThenExecutor};
-use crate::{BuildExecute, ChildUsage, CommandArgument, CommandError, Execute, IntoMultipleUsage, TaskLogic, UsagePrint};
+use super::inherit::Inherited;
+use super::literal::{eq_case, literal_word};
+use super::{LiteralExecutor, LiteralThen, LiteralThenExecutor, Separator};
+use crate::argument::box_task;
+use crate::bound::Bound;
+use crate::error::{next_token, Expected};
+use crate::{
+    AsyncExecute, AsyncTaskLogic, BoundCommand, BuildAsyncExecute, BuildExecute, ChildUsage, CommandArgument, CommandError, CommandFuture,
+    CommandNode, CommandTree, Execute, IntoMultipleUsage, NodeKind, ParseCommand, SmartUsage, TaskLogic, UsagePrint,
+};
 
 /// Parser that parses a root command followed by `" help"`.
 ///
 /// This parser produces an iterator over all the different usages the root
-/// parser can parse.
+/// parser can parse. Literals in between the root and `help` (e.g. `foo bar
+/// help`) limit the usages to the ones below that literal node.
 pub struct HelpArgument<S, E> {
     pub(crate) argument: E,
     pub(crate) description: &'static str,
+    pub(crate) keyword: &'static str,
+    pub(crate) case_sensitive: bool,
+    pub(crate) separator: Option<Separator>,
     pub(crate) source: PhantomData<S>,
 }
 
+impl<S, E> HelpArgument<S, E> {
+    /// Replace the `help` keyword, e.g. with `?` or a localized word.
+    pub fn keyword(mut self, keyword: &'static str) -> Self {
+        self.keyword = keyword;
+        self
+    }
+
+    /// Only accept the exact `help` keyword, by default it ignores case.
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+}
+
 /// Type that can produce a usage list and help command.
 ///
 /// This should conventionally only be implemented on literal types (types that
@@ -33,6 +57,9 @@ pub trait ThenHelp<S> {
         HelpArgument {
             argument: self,
             description,
+            keyword: "help",
+            case_sensitive: false,
+            separator: None,
             source: PhantomData,
         }
     }
@@ -40,14 +67,41 @@ pub trait ThenHelp<S> {
 
 impl<S, E> CommandArgument<S, UsagePrint<E::Item>> for HelpArgument<S, E>
 where
-    E: CommandArgument<S, ()> + IntoMultipleUsage,
+    E: CommandArgument<S, ()> + IntoMultipleUsage + CommandTree,
 {
     fn parse<'a>(&self, source: S, input: &'a str) -> nom::IResult<&'a str, UsagePrint<E::Item>, crate::CommandError<'a>> {
-        let (input, _) = self.argument.parse(source, input)?;
-        let (input, _) = char(' ')(input)?;
-        let (input, _) = tag_no_case("help")(input)?;
+        let (mut input, _) = self.argument.parse(source, input)?;
+        let mut path = Vec::new();
+        let mut children: Option<Vec<CommandNode>> = None;
+        loop {
+            let (rest, _) = Inherited::separator(self.separator).parse(input)?;
+            if let Ok((rest, _)) = literal_word(self.keyword.into(), self.case_sensitive, rest) {
+                input = rest;
+                break;
+            }
+            // Only literal nodes can be walked, arguments end the help path.
+            let nodes = children.get_or_insert_with(|| self.argument.command_nodes().into_iter().flat_map(|n| n.children).collect());
+            let word = next_token(rest);
+            let literal = nodes
+                .iter()
+                .position(|n| matches!(n.kind, NodeKind::Literal) && eq_case(&n.name, word, Inherited::case_sensitive(n.case_sensitive)));
+            let Some(literal) = literal else {
+                let literals = nodes
+                    .iter()
+                    .filter(|n| matches!(n.kind, NodeKind::Literal))
+                    .map(|n| Expected::Literal(n.name.clone()));
+                let expected = std::iter::once(Expected::Literal(self.keyword.into())).chain(literals).collect();
+                return Err(nom::Err::Error(CommandError::unknown_literal(rest, expected)));
+            };
+            let node = nodes.swap_remove(literal);
+            path.push(node.name.into_owned());
+            *nodes = node.children;
+            input = &rest[word.len()..];
+        }
         Ok((input, UsagePrint {
             usage: self.argument.usage_gen(),
+            typed: false,
+            path,
         }))
     }
 }
@@ -67,6 +121,54 @@ where
     fn execute<'a>(&self, source: S, input: &'a str) -> nom::IResult<&'a str, U, CommandError<'a>> { self.argument.execute(source, input) }
 }
 
+impl<S, E, C> BuildAsyncExecute<C, HelpExecutor<S, E, C>> for HelpArgument<S, E>
+where
+    E: IntoMultipleUsage,
+    C: AsyncTaskLogic<S, UsagePrint<E::Item>>,
+{
+    fn build_async_exec(self, task: C) -> HelpExecutor<S, E, C> { HelpExecutor { help: self, task } }
+}
+
+impl<E, S, U> ParseCommand<S, U> for HelpArgument<S, E>
+where
+    E: ParseCommand<S, U>,
+{
+    fn parse_command<'a>(&self, source: S, input: &'a str) -> nom::IResult<&'a str, BoundCommand<'_, S, U>, CommandError<'a>> {
+        self.argument.parse_command(source, input)
+    }
+}
+
+impl<E, S, U> AsyncExecute<S, U> for HelpArgument<S, E>
+where
+    E: AsyncExecute<S, U>,
+{
+    fn execute_async<'a>(&self, source: S, input: &'a str) -> nom::IResult<&'a str, CommandFuture<U>, CommandError<'a>> {
+        self.argument.execute_async(source, input)
+    }
+}
+
+impl<S, E> SmartUsage for HelpArgument<S, E>
+where
+    E: SmartUsage,
+{
+    fn node_count(&self) -> usize { self.argument.node_count() }
+
+    fn node_usage<W: std::fmt::Write>(&self, writer: &mut W) -> Result<(), std::fmt::Error> { self.argument.node_usage(writer) }
+
+    fn smart_usage<W: std::fmt::Write>(&self, writer: &mut W, optional: bool, depth: usize) -> Result<(), std::fmt::Error> {
+        self.argument.smart_usage(writer, optional, depth)
+    }
+}
+
+impl<S, E> IntoMultipleUsage for HelpArgument<S, E>
+where
+    E: IntoMultipleUsage,
+{
+    type Item = E::Item;
+
+    fn usage_gen(&self) -> Self::Item { self.argument.usage_gen() }
+}
+
 /// Executor for a custom help message.
 ///
 /// Similar to [`DefaultExecutor`](crate::parsers::DefaultExecutor).
@@ -77,7 +179,7 @@ pub struct HelpExecutor<S, E, C> {
 
 impl<E, C, U, S> Execute<S, U> for HelpExecutor<S, E, C>
 where
-    E: Execute<S, U> + CommandArgument<S, ()> + IntoMultipleUsage,
+    E: Execute<S, U> + CommandArgument<S, ()> + IntoMultipleUsage + CommandTree,
     C: TaskLogic<S, UsagePrint<E::Item>, Output = U>,
     S: Copy,
 {
@@ -95,6 +197,69 @@ where
     }
 }
 
+impl<E, C, U, S> ParseCommand<S, U> for HelpExecutor<S, E, C>
+where
+    E: ParseCommand<S, U> + CommandArgument<S, ()> + IntoMultipleUsage + CommandTree,
+    E::Item: Clone + 'static,
+    C: TaskLogic<S, UsagePrint<E::Item>, Output = U>,
+    S: Copy,
+{
+    fn parse_command<'a>(&self, source: S, input: &'a str) -> nom::IResult<&'a str, BoundCommand<'_, S, U>, CommandError<'a>> {
+        alt((
+            |i| {
+                let (input, usage) = self.help.parse(source, i)?;
+                Ok((
+                    input,
+                    BoundCommand::new(Bound {
+                        task: &self.task,
+                        args: usage,
+                    }),
+                ))
+            },
+            |i| self.help.argument.parse_command(source, i),
+        ))(input)
+    }
+}
+
+impl<E, C, U, S> AsyncExecute<S, U> for HelpExecutor<S, E, C>
+where
+    E: AsyncExecute<S, U> + CommandArgument<S, ()> + IntoMultipleUsage + CommandTree,
+    C: AsyncTaskLogic<S, UsagePrint<E::Item>, Output = U>,
+    S: Copy,
+{
+    fn execute_async<'a>(&self, source: S, input: &'a str) -> nom::IResult<&'a str, CommandFuture<U>, CommandError<'a>> {
+        alt((
+            |i| {
+                let (input, usage) = self.help.parse(source, i)?;
+                Ok((input, box_task(self.task.run(source, usage))))
+            },
+            |i| self.help.argument.execute_async(source, i),
+        ))(input)
+    }
+}
+
+impl<S, E, C> SmartUsage for HelpExecutor<S, E, C>
+where
+    E: SmartUsage,
+{
+    fn node_count(&self) -> usize { self.help.argument.node_count() }
+
+    fn node_usage<W: std::fmt::Write>(&self, writer: &mut W) -> Result<(), std::fmt::Error> { self.help.argument.node_usage(writer) }
+
+    fn smart_usage<W: std::fmt::Write>(&self, writer: &mut W, optional: bool, depth: usize) -> Result<(), std::fmt::Error> {
+        self.help.argument.smart_usage(writer, optional, depth)
+    }
+}
+
+impl<S, E, C> IntoMultipleUsage for HelpExecutor<S, E, C>
+where
+    E: IntoMultipleUsage,
+{
+    type Item = E::Item;
+
+    fn usage_gen(&self) -> Self::Item { self.help.argument.usage_gen() }
+}
+
 /// Name and description of a command.
 ///
 /// This is primarily meant to generate quick overviews of available parser
@@ -105,6 +270,10 @@ pub struct HelpEntry {
     pub description: Cow<'static, str>,
 }
 
+impl std::fmt::Display for HelpEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { write!(f, "{}: {}", self.name, self.description) }
+}
+
 /// Type that returns a [`HelpEntry`].
 pub trait HelpUsage {
     /// Returns name and description of this type in a [`HelpEntry`].
@@ -113,11 +282,11 @@ pub trait HelpUsage {
 
 impl<S, E> HelpUsage for HelpArgument<S, E>
 where
-    E: ChildUsage<Child = &'static str>,
+    E: ChildUsage<Child = Cow<'static, str>>,
 {
     fn help(&self) -> HelpEntry {
         HelpEntry {
-            name: self.argument.usage_child().into(),
+            name: self.argument.usage_child(),
             description: self.description.into(),
         }
     }
@@ -125,11 +294,11 @@ where
 
 impl<S, E, C> HelpUsage for HelpExecutor<S, E, C>
 where
-    E: ChildUsage<Child = &'static str>,
+    E: ChildUsage<Child = Cow<'static, str>>,
 {
     fn help(&self) -> HelpEntry {
         HelpEntry {
-            name: self.help.argument.usage_child().into(),
+            name: self.help.argument.usage_child(),
             description: self.help.description.into(),
         }
     }
@@ -140,3 +309,79 @@ impl<A, C, S> ThenHelp<S> for LiteralExecutor<A, C, S> {}
 impl<A, E, C, S> ThenHelp<S> for LiteralThenExecutor<A, E, C, S> {}
 
 impl<A, E, S> ThenHelp<S> for LiteralThen<A, E, S> {}
+
+impl<S, E> CommandTree for HelpArgument<S, E>
+where
+    E: CommandTree,
+{
+    fn command_nodes(&self) -> Vec<CommandNode> {
+        let mut nodes = self.argument.command_nodes();
+        for node in &mut nodes {
+            node.description = Some(self.description.into());
+        }
+        nodes
+    }
+}
+
+impl<S, E, C> CommandTree for HelpExecutor<S, E, C>
+where
+    E: CommandTree,
+{
+    fn command_nodes(&self) -> Vec<CommandNode> {
+        let mut help = CommandNode::literal(self.help.keyword);
+        help.executable = true;
+        help.case_sensitive = self.help.case_sensitive;
+        let mut nodes = self.help.command_nodes();
+        for node in &mut nodes {
+            push_help(node, &help);
+        }
+        nodes
+    }
+}
+
+/// Add `help` below `node` and every literal reachable through literals, the
+/// same nodes a help path can walk.
+fn push_help(node: &mut CommandNode, help: &CommandNode) {
+    for child in &mut node.children {
+        if matches!(child.kind, NodeKind::Literal) {
+            push_help(child, help);
+        }
+    }
+    node.children.push(help.clone());
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use nom::Finish;
+
+    use crate::parsers::help::ThenHelp;
+    use crate::{integer_i32, literal, BuildExecute, Execute, Then, UsagePrint};
+
+    #[test]
+    fn test_nested_help() {
+        let parser = literal("foo")
+            .then(
+                literal("bar")
+                    .then(integer_i32("baz").build_exec(|_: (), _| Ok::<Vec<String>, Infallible>(vec![])))
+                    .then(literal("all").build_exec(|_: ()| Ok::<Vec<String>, Infallible>(vec![]))),
+            )
+            .then(literal("qux").build_exec(|_: ()| Ok::<Vec<String>, Infallible>(vec![])))
+            .help("Test description")
+            .keyword("?")
+            .build_exec(|_: (), usages: UsagePrint<_>| Ok::<_, Infallible>(usages.collect::<Result<Vec<_>, _>>().unwrap()));
+
+        let (_, usages) = parser.execute((), "foo ?").unwrap();
+        assert_eq!(vec!["foo bar <baz>", "foo bar all", "foo qux"], usages);
+        let (_, usages) = parser.execute((), "foo BAR ?").unwrap();
+        assert_eq!(vec!["foo bar <baz>", "foo bar all"], usages);
+        let (_, usages) = parser.execute((), "foo bar all ?").unwrap();
+        assert_eq!(vec!["foo bar all"], usages);
+        assert_eq!("Expected one of: ?, bar, qux", parser.execute((), "foo help").finish().unwrap_err().kind.to_string());
+        assert_eq!("Expected one of: ?, all, <baz>", parser.execute((), "foo bar <baz> ?").finish().unwrap_err().kind.to_string());
+        let error = parser.execute((), "foo bar nope ?").finish().unwrap_err();
+        assert_eq!("nope", error.token());
+        assert_eq!("Expected one of: ?, all, <baz>", error.kind.to_string());
+    }
+}
@@ -0,0 +1,57 @@
+use std::cell::Cell;
+
+use super::Separator;
+
+/// Policies a parser passes down to its children while parsing, `None`
+/// leaves the policy of the parent in place.
+///
+/// Policies are resolved while parsing so they also reach children added
+/// later and boxed branches, the outermost parser that sets a policy wins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) struct Inherited {
+    pub(crate) separator: Option<Separator>,
+    pub(crate) case_sensitive: Option<bool>,
+}
+
+thread_local! {
+    /// Policies of the parsers that are currently parsing on this thread.
+    static CURRENT: Cell<Inherited> = const {
+        Cell::new(Inherited {
+            separator: None,
+            case_sensitive: None,
+        })
+    };
+}
+
+/// Restores the previous policies when the parser that set them returns.
+struct Restore(Inherited);
+
+impl Drop for Restore {
+    fn drop(&mut self) { CURRENT.with(|current| current.set(self.0)); }
+}
+
+impl Inherited {
+    /// Separator policy of a parser with its own `separator`.
+    pub(crate) fn separator(separator: Option<Separator>) -> Separator {
+        CURRENT.with(Cell::get).separator.or(separator).unwrap_or_default()
+    }
+
+    /// Case sensitivity of a literal with its own `case_sensitive` setting.
+    pub(crate) fn case_sensitive(case_sensitive: bool) -> bool { CURRENT.with(Cell::get).case_sensitive.unwrap_or(case_sensitive) }
+
+    /// Run `f` with these policies inherited by all parsers below, unless a
+    /// parent already set them.
+    pub(crate) fn scope<R>(self, f: impl FnOnce() -> R) -> R {
+        let previous = CURRENT.with(Cell::get);
+        let current = Inherited {
+            separator: previous.separator.or(self.separator),
+            case_sensitive: previous.case_sensitive.or(self.case_sensitive),
+        };
+        if current == previous {
+            return f();
+        }
+        CURRENT.with(|c| c.set(current));
+        let _restore = Restore(previous);
+        f()
+    }
+}
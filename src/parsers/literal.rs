@@ -1,38 +1,87 @@
+use std::borrow::Cow;
 use std::marker::PhantomData;
 
-use nom::bytes::complete::tag_no_case;
 use nom::error::{ErrorKind, FromExternalError};
 use nom::IResult;
 
+use super::inherit::Inherited;
 use super::LiteralThen;
+use crate::argument::box_task;
+use crate::bound::{Bound, BoundNoArgs};
+use crate::error::{next_token, Expected};
+use crate::tree::executable_nodes;
+use crate::usage::smart::LeafUsage;
 use crate::{
-    BuildExecute, BuildPropagate, ChildUsage, CommandArgument, CommandError, Execute, IntoMultipleUsage, Propagate, TaskLogic,
-    TaskLogicNoArgs, Then,
+    AsyncExecute, AsyncPropagate, AsyncTaskLogic, AsyncTaskLogicNoArgs, BoundCommand, BuildAsyncExecute, BuildAsyncPropagate, BuildExecute,
+    BuildPropagate, ChildUsage, CmdErrorKind, CommandArgument, CommandError, CommandFuture, CommandNode, CommandTree, Execute,
+    IntoMultipleUsage, ParseCommand, ParsePropagate, Propagate, TaskLogic, TaskLogicNoArgs, Then,
 };
 
 /// Create a new literal parser
 ///
-/// This parser has 1 field; the literal that should be matched against.
-pub fn literal<S>(literal: &'static str) -> LiteralArgument<S> {
+/// This parser has 1 field; the literal that should be matched against, which
+/// can be a `&'static str` or a [`String`] built at runtime.
+pub fn literal<S>(literal: impl Into<Cow<'static, str>>) -> LiteralArgument<S> {
     LiteralArgument {
-        literal,
+        literal: literal.into(),
+        case_sensitive: false,
         source: PhantomData,
     }
 }
 
 /// Literal argument parser.
 pub struct LiteralArgument<S> {
-    literal: &'static str,
+    literal: Cow<'static, str>,
+    pub(crate) case_sensitive: bool,
     source: PhantomData<S>,
 }
 
+impl<S> LiteralArgument<S> {
+    /// Only match the literal with the exact same case, literals ignore case
+    /// by default.
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+}
+
 impl<S> CommandArgument<S, ()> for LiteralArgument<S> {
     fn parse<'a>(&self, _source: S, input: &'a str) -> IResult<&'a str, (), CommandError<'a>> {
-        let (output, _) = tag_no_case(self.literal)(input)?;
-        Ok((output, ()))
+        literal_word(self.literal.clone(), self.case_sensitive, input)
+    }
+}
+
+/// Returns true if `a` and `b` are equal, ignoring case unless
+/// `case_sensitive`.
+pub(crate) fn eq_case(a: &str, b: &str, case_sensitive: bool) -> bool {
+    if case_sensitive {
+        a == b
+    } else {
+        a.chars().flat_map(char::to_lowercase).eq(b.chars().flat_map(char::to_lowercase))
     }
 }
 
+/// Match a whole word against `literal`, ignoring case unless
+/// `case_sensitive` or a parent makes it case sensitive.
+///
+/// The literal must be followed by a space or the end of the input, so
+/// `foo` does not match the start of `foobar`.
+pub(crate) fn literal_word<'a>(literal: Cow<'static, str>, case_sensitive: bool, input: &'a str) -> IResult<&'a str, (), CommandError<'a>> {
+    let token = next_token(input);
+    if eq_case(token, &literal, Inherited::case_sensitive(case_sensitive)) {
+        return Ok((&input[token.len()..], ()));
+    }
+    let kind = if token.is_empty() {
+        CmdErrorKind::ExpectedLiteral(literal.clone())
+    } else {
+        CmdErrorKind::UnknownLiteral {
+            expected: literal.clone(),
+            found: token.to_owned(),
+        }
+    };
+    Err(nom::Err::Error(CommandError::from_external_error(input, ErrorKind::Tag, kind).with_expected(Expected::Literal(literal))))
+}
+
 impl<S, E> Then<E> for LiteralArgument<S> {
     type Output = LiteralThen<Self, E, S>;
 
@@ -40,6 +89,7 @@ impl<S, E> Then<E> for LiteralArgument<S> {
         LiteralThen {
             argument: self,
             executor,
+            inherited: Inherited::default(),
             source: PhantomData,
         }
     }
@@ -71,23 +121,51 @@ where
     }
 }
 
+impl<S, C> BuildAsyncExecute<C, LiteralExecutor<LiteralArgument<S>, C, S>> for LiteralArgument<S>
+where
+    C: AsyncTaskLogicNoArgs<S>,
+{
+    fn build_async_exec(self, task: C) -> LiteralExecutor<LiteralArgument<S>, C, S> {
+        LiteralExecutor {
+            argument: self,
+            task,
+            source: PhantomData,
+        }
+    }
+}
+
+impl<S, C, T> BuildAsyncPropagate<C, T, LiteralExecutor<Self, C, S>> for LiteralArgument<S>
+where
+    C: AsyncTaskLogic<S, T>,
+{
+    fn build_async_propagate(self, task: C) -> LiteralExecutor<LiteralArgument<S>, C, S> {
+        LiteralExecutor {
+            argument: self,
+            task,
+            source: PhantomData,
+        }
+    }
+}
+
 impl<S> IntoMultipleUsage for LiteralArgument<S> {
-    type Item = <&'static str as IntoMultipleUsage>::Item;
+    type Item = <Cow<'static, str> as IntoMultipleUsage>::Item;
 
     fn usage_gen(&self) -> Self::Item { self.usage_child().usage_gen() }
 }
 
 impl<S> ChildUsage for LiteralArgument<S> {
-    type Child = &'static str;
+    type Child = Cow<'static, str>;
 
-    fn usage_child(&self) -> Self::Child { self.literal }
+    fn usage_child(&self) -> Self::Child { self.literal.clone() }
 }
 
+impl<S> LeafUsage for LiteralArgument<S> {}
+
 /// Type returned when calling [`build_exec`](BuildExecute::build_exec) or
 /// [`build_propagate`](BuildPropagate::build_propagate) on a
 /// [`LiteralArgument`].
 pub struct LiteralExecutor<A, C, S> {
-    argument: A,
+    pub(crate) argument: A,
     task: C,
     source: PhantomData<S>,
 }
@@ -123,6 +201,62 @@ where
     }
 }
 
+impl<A, C, U, S> ParseCommand<S, U> for LiteralExecutor<A, C, S>
+where
+    S: Copy,
+    A: CommandArgument<S, ()>,
+    C: TaskLogicNoArgs<S, Output = U>,
+{
+    fn parse_command<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, BoundCommand<'_, S, U>, CommandError<'a>> {
+        let (input, _) = self.argument.parse(source, input)?;
+        Ok((input, BoundCommand::new(BoundNoArgs { task: &self.task })))
+    }
+}
+
+impl<A, C, T, U, S> ParsePropagate<S, T, U> for LiteralExecutor<A, C, S>
+where
+    T: Copy + 'static,
+    S: Copy,
+    A: CommandArgument<S, ()>,
+    C: TaskLogic<S, T, Output = U>,
+{
+    fn parse_propagate<'a>(&self, source: S, input: &'a str, data: T) -> IResult<&'a str, BoundCommand<'_, S, U>, CommandError<'a>> {
+        let (input, _) = self.argument.parse(source, input)?;
+        Ok((
+            input,
+            BoundCommand::new(Bound {
+                task: &self.task,
+                args: data,
+            }),
+        ))
+    }
+}
+
+impl<A, C, U, S> AsyncExecute<S, U> for LiteralExecutor<A, C, S>
+where
+    S: Copy,
+    A: CommandArgument<S, ()>,
+    C: AsyncTaskLogicNoArgs<S, Output = U>,
+{
+    fn execute_async<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, CommandFuture<U>, CommandError<'a>> {
+        let (input, _) = self.argument.parse(source, input)?;
+        Ok((input, box_task(self.task.run(source))))
+    }
+}
+
+impl<A, C, T, U, S> AsyncPropagate<S, T, U> for LiteralExecutor<A, C, S>
+where
+    T: Copy,
+    S: Copy,
+    A: CommandArgument<S, ()>,
+    C: AsyncTaskLogic<S, T, Output = U>,
+{
+    fn propagate_async<'a>(&self, source: S, input: &'a str, data: T) -> IResult<&'a str, CommandFuture<U>, CommandError<'a>> {
+        let (input, _) = self.argument.parse(source, input)?;
+        Ok((input, box_task(self.task.run(source, data))))
+    }
+}
+
 impl<A, C, S> CommandArgument<S, ()> for LiteralExecutor<A, C, S>
 where
     A: CommandArgument<S, ()>,
@@ -147,3 +281,20 @@ where
 
     fn usage_child(&self) -> Self::Child { self.argument.usage_child() }
 }
+
+impl<A, C, S> LeafUsage for LiteralExecutor<A, C, S> where A: ChildUsage {}
+
+impl<S> CommandTree for LiteralArgument<S> {
+    fn command_nodes(&self) -> Vec<CommandNode> {
+        let mut node = CommandNode::literal(self.literal.clone());
+        node.case_sensitive = self.case_sensitive;
+        vec![node]
+    }
+}
+
+impl<A, C, S> CommandTree for LiteralExecutor<A, C, S>
+where
+    A: CommandTree,
+{
+    fn command_nodes(&self) -> Vec<CommandNode> { executable_nodes(&self.argument) }
+}
@@ -1,3 +1,4 @@
+use std::fmt::Display;
 use std::marker::PhantomData;
 
 use nom::bytes::complete::tag;
@@ -8,9 +9,14 @@ use nom::multi::{many0, many1};
 use nom::sequence::{preceded, separated_pair, terminated};
 use nom::IResult;
 
+use super::inherit::Inherited;
 use super::CommandThen;
-use crate::error::CmdErrorKind;
-use crate::{ArgumentMarkerDefaultImpl, ChildUsage, CommandArgument, CommandError, IntoMultipleUsage, Then};
+use crate::error::{CmdErrorKind, Expected, NumberKind};
+use crate::usage::smart::LeafUsage;
+use crate::{
+    ArgumentMarkerDefaultImpl, ArgumentUsage, ChildUsage, CommandArgument, CommandError, CommandNode, CommandTree, IntoMultipleUsage,
+    NodeProperty, Then,
+};
 
 /// Numeric argument parser.
 ///
@@ -20,7 +26,11 @@ pub struct NumberArgument<N, S> {
     pub(crate) name: &'static str,
     pub(crate) min: N,
     pub(crate) max: N,
-    pub(crate) parse: fn(&str) -> IResult<&str, N, CommandError>,
+    pub(crate) kind: NumberKind,
+    pub(crate) type_name: &'static str,
+    pub(crate) bounded_min: bool,
+    pub(crate) bounded_max: bool,
+    pub(crate) parse: fn(&str) -> IResult<&str, N, CommandError<'_>>,
     pub(crate) source: PhantomData<S>,
 }
 
@@ -32,6 +42,7 @@ where
     pub fn min(mut self, min: N) -> Self {
         debug_assert!(self.max >= min);
         self.min = min;
+        self.bounded_min = true;
         self
     }
 
@@ -39,23 +50,36 @@ where
     pub fn max(mut self, max: N) -> Self {
         debug_assert!(self.min <= max);
         self.max = max;
+        self.bounded_max = true;
         self
     }
 }
 
 impl<S, N> CommandArgument<S, N> for NumberArgument<N, S>
 where
-    N: PartialOrd,
+    N: PartialOrd + Display,
 {
     /// This implementation may return a [`Failure`](nom::Err::Failure) when the
     /// parsed number is outside of the bounds.
     fn parse<'a>(&self, _source: S, input: &'a str) -> nom::IResult<&'a str, N, CommandError<'a>> {
-        let (input, out) = (self.parse)(input)?;
-        if out > self.max || out < self.min {
-            Err(nom::Err::Failure(CommandError::from_external_error(input, ErrorKind::MapRes, CmdErrorKind::OutOfBounds)))
+        let (rest, out) = (self.parse)(input).map_err(|e| e.map(|e| e.with_expected(Expected::Argument(self.name.into()))))?;
+        let kind = if out < self.min {
+            CmdErrorKind::TooLow {
+                number: self.kind,
+                min: self.min.to_string(),
+                found: out.to_string(),
+            }
+        } else if out > self.max {
+            CmdErrorKind::TooHigh {
+                number: self.kind,
+                max: self.max.to_string(),
+                found: out.to_string(),
+            }
         } else {
-            Ok((input, out))
-        }
+            return Ok((rest, out));
+        };
+        let error = CommandError::from_external_error(rest, ErrorKind::MapRes, kind);
+        Err(nom::Err::Failure(error.with_token(&input[..input.len() - rest.len()])))
     }
 }
 
@@ -66,31 +90,52 @@ impl<E, N, S> Then<E> for NumberArgument<N, S> {
         CommandThen {
             argument: self,
             executor,
+            inherited: Inherited::default(),
             output: PhantomData,
             source: PhantomData,
         }
     }
 }
 
-impl<N, S> IntoMultipleUsage for NumberArgument<N, S> {
-    type Item = <[&'static str; 3] as IntoMultipleUsage>::Item;
+impl<N, S> IntoMultipleUsage for NumberArgument<N, S>
+where
+    N: Display,
+{
+    type Item = <ArgumentUsage as IntoMultipleUsage>::Item;
 
     fn usage_gen(&self) -> Self::Item { self.usage_child().usage_gen() }
 }
 
-impl<N, S> ChildUsage for NumberArgument<N, S> {
-    type Child = [&'static str; 3];
-
-    fn usage_child(&self) -> Self::Child { ["<", self.name, ">"] }
+impl<N, S> ChildUsage for NumberArgument<N, S>
+where
+    N: Display,
+{
+    type Child = ArgumentUsage;
+
+    fn usage_child(&self) -> Self::Child {
+        let constraint = match (self.bounded_min, self.bounded_max) {
+            (false, false) => None,
+            (true, false) => Some(format!("{}..", self.min)),
+            (false, true) => Some(format!("..{}", self.max)),
+            (true, true) => Some(format!("{}..{}", self.min, self.max)),
+        };
+        ArgumentUsage {
+            name: self.name,
+            type_name: self.type_name,
+            constraint,
+        }
+    }
 }
 
+impl<N, S> LeafUsage for NumberArgument<N, S> where N: Display {}
+
 impl<N, S> ArgumentMarkerDefaultImpl for NumberArgument<N, S> {}
 
-fn decimal(input: &str) -> IResult<&str, &str, CommandError> {
+fn decimal(input: &str) -> IResult<&str, &str, CommandError<'_>> {
     recognize(preceded(opt(tag("-")), many1(terminated(one_of("0123456789"), many0(char('_'))))))(input)
 }
 
-fn float(input: &str) -> IResult<&str, &str, CommandError> {
+fn float(input: &str) -> IResult<&str, &str, CommandError<'_>> {
     recognize(preceded(
         opt(tag("-")),
         separated_pair(
@@ -102,38 +147,81 @@ fn float(input: &str) -> IResult<&str, &str, CommandError> {
 }
 
 macro_rules! impl_num {
-    ($num:ty => $name:ident = $parse:ident) => {
-        impl_num!($num => $name = $parse + decimal);
+    ($num:ty as $type_name:literal => $name:ident = $parse:ident, $kind:ident) => {
+        impl_num!($num as $type_name => $name = $parse + decimal, $kind, <$num>::MAX);
+    };
+    ($num:ty as $type_name:literal => $name:ident = $parse:ident, $kind:ident, $max:expr) => {
+        impl_num!($num as $type_name => $name = $parse + decimal, $kind, $max);
     };
-    ($num:ty => $name:ident = $parse:ident + $num_parse:ident) => {
+    ($num:ty as $type_name:literal => $name:ident = $parse:ident + $num_parse:ident, $kind:ident) => {
+        impl_num!($num as $type_name => $name = $parse + $num_parse, $kind, <$num>::MAX);
+    };
+    ($num:ty as $type_name:literal => $name:ident = $parse:ident + $num_parse:ident, $kind:ident, $max:expr) => {
         #[doc = stringify!(Create a $num argument parser.)]
         pub fn $name<S>(name: &'static str) -> NumberArgument<$num, S> {
             NumberArgument {
                 name,
                 min: <$num>::MIN,
-                max: <$num>::MAX,
+                max: $max,
+                kind: NumberKind::$kind,
+                type_name: $type_name,
+                bounded_min: false,
+                bounded_max: false,
                 parse: $parse,
                 source: PhantomData,
             }
         }
 
-        fn $parse(input: &str) -> IResult<&str, $num, CommandError> {
-            let (input, number) = $num_parse(input)?;
+        fn $parse(input: &str) -> IResult<&str, $num, CommandError<'_>> {
+            let (input, number) = $num_parse(input).map_err(|e| {
+                e.map(|_| CommandError::from_external_error(input, ErrorKind::Digit, CmdErrorKind::ExpectedNumber(NumberKind::$kind)))
+            })?;
             match ::std::str::FromStr::from_str(number) {
-                Err(e) => Err(nom::Err::Failure(CommandError::from_external_error(input, ::nom::error::ErrorKind::MapRes, e))),
+                Err(_) => {
+                    let kind = CmdErrorKind::InvalidNumber {
+                        number: NumberKind::$kind,
+                        found: number.to_owned(),
+                    };
+                    let error = CommandError::from_external_error(input, ::nom::error::ErrorKind::MapRes, kind);
+                    Err(nom::Err::Failure(error.with_token(number)))
+                },
                 Ok(v) => Ok((input, v)),
             }
         }
     };
 }
 
-impl_num!(i8 => integer_i8 = parse_i8);
-impl_num!(i16 => integer_i16 = parse_i16);
-impl_num!(i32 => integer_i32 = parse_i32);
-impl_num!(i64 => integer_i64 = parse_i64);
-impl_num!(u8 => integer_u8 = parse_u8);
-impl_num!(u16 => integer_u16 = parse_u16);
-impl_num!(u32 => integer_u32 = parse_u32);
-impl_num!(u64 => integer_u64 = parse_u64);
-impl_num!(f32 => float_32 = parse_f32 + float);
-impl_num!(f64 => float_64 = parse_f64 + float);
+impl_num!(i8 as "i8" => integer_i8 = parse_i8, Integer);
+impl_num!(i16 as "i16" => integer_i16 = parse_i16, Integer);
+impl_num!(i32 as "i32" => integer_i32 = parse_i32, Integer);
+impl_num!(i64 as "i64" => integer_i64 = parse_i64, Long);
+impl_num!(u8 as "u8" => integer_u8 = parse_u8, Integer);
+impl_num!(u16 as "u16" => integer_u16 = parse_u16, Integer);
+impl_num!(u32 as "u32" => integer_u32 = parse_u32, Long);
+// Vanilla longs are signed, larger values could not be sent to clients.
+impl_num!(u64 as "u64" => integer_u64 = parse_u64, Long, i64::MAX as u64);
+impl_num!(f32 as "f32" => float_32 = parse_f32 + float, Float);
+impl_num!(f64 as "f64" => float_64 = parse_f64 + float, Double);
+
+impl<N, S> CommandTree for NumberArgument<N, S>
+where
+    N: Copy + Into<NodeProperty>,
+{
+    fn command_nodes(&self) -> Vec<CommandNode> {
+        let mut node = CommandNode::argument(self.name, format!("brigadier:{}", self.kind));
+        // Types narrower than the vanilla parser (e.g. `u8` as an integer) need
+        // their own bounds even when none were set.
+        let (min, max) = match self.kind {
+            NumberKind::Integer => (i32::MIN.into(), i32::MAX.into()),
+            NumberKind::Long => (i64::MIN.into(), i64::MAX.into()),
+            NumberKind::Float | NumberKind::Double => (self.min.into(), self.max.into()),
+        };
+        if self.bounded_min || self.min.into() != min {
+            node = node.with_property("min", self.min);
+        }
+        if self.bounded_max || self.max.into() != max {
+            node = node.with_property("max", self.max);
+        }
+        vec![node]
+    }
+}
@@ -0,0 +1,216 @@
+use nom::error::{ErrorKind, FromExternalError};
+use nom::IResult;
+
+use super::inherit::Inherited;
+use super::{
+    Branches, Choice, CommandThen, DefaultExecutor, Described, HelpArgument, HelpExecutor, LiteralExecutor, LiteralThen,
+    LiteralThenExecutor, ThenExecutor, ThenWrapper,
+};
+use crate::{CmdErrorKind, CommandError};
+
+/// Policy for the whitespace between arguments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Separator {
+    /// Exactly one space, like vanilla. Any other whitespace returns
+    /// [`CmdErrorKind::UnexpectedWhitespace`].
+    #[default]
+    Strict,
+    /// One or more whitespace characters of any kind.
+    Lenient,
+}
+
+impl Separator {
+    pub(crate) fn parse(self, input: &str) -> IResult<&str, (), CommandError<'_>> {
+        let end = input.find(|c: char| !c.is_whitespace()).unwrap_or(input.len());
+        let kind = match self {
+            _ if input.is_empty() => CmdErrorKind::IncompleteCommand,
+            _ if end == 0 => CmdErrorKind::ExpectedSeparator,
+            Separator::Strict if !input.starts_with(' ') => CmdErrorKind::UnexpectedWhitespace,
+            Separator::Strict if end > 1 => {
+                let error = CommandError::from_external_error(&input[1..], ErrorKind::Space, CmdErrorKind::UnexpectedWhitespace);
+                return Err(nom::Err::Error(error.with_token(&input[1..end])));
+            },
+            Separator::Strict => return Ok((&input[1..], ())),
+            Separator::Lenient => return Ok((&input[end..], ())),
+        };
+        let error = CommandError::from_external_error(input, ErrorKind::Space, kind);
+        Err(nom::Err::Error(error.with_token(&input[..end])))
+    }
+}
+
+/// Parse a separator and then the children of a parser with `child`, the
+/// children inherit the policies of `inherited`.
+pub(crate) fn separated<'a, T, F>(inherited: Inherited, input: &'a str, child: F) -> IResult<&'a str, T, CommandError<'a>>
+where
+    F: FnOnce(&'a str) -> IResult<&'a str, T, CommandError<'a>>,
+{
+    inherited.scope(|| {
+        let (input, _) = Inherited::separator(None).parse(input)?;
+        child(input)
+    })
+}
+
+/// Parser with arguments separated according to a [`Separator`] policy.
+///
+/// Setting the policy on a parser applies it to all of its children, including
+/// the ones added with [`Then::then`](crate::Then::then) afterwards and boxed
+/// [`Branches`], so it is usually set once on the root parser. The policy is
+/// resolved while parsing, a policy set on a parent overrides the ones set on
+/// its children.
+pub trait WithSeparator {
+    /// Set the separator policy of this parser and all of its children.
+    fn set_separator(&mut self, separator: Separator);
+
+    /// Returns this parser using `separator` between its arguments.
+    fn separator(mut self, separator: Separator) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_separator(separator);
+        self
+    }
+}
+
+impl<A, C, O, S> WithSeparator for DefaultExecutor<A, C, O, S> {
+    fn set_separator(&mut self, _separator: Separator) {}
+}
+
+impl<A, C, S> WithSeparator for LiteralExecutor<A, C, S> {
+    fn set_separator(&mut self, _separator: Separator) {}
+}
+
+impl<A, E, O, S> WithSeparator for CommandThen<A, E, O, S> {
+    fn set_separator(&mut self, separator: Separator) { self.inherited.separator = Some(separator); }
+}
+
+impl<A, E, C, O, S> WithSeparator for ThenExecutor<A, E, C, O, S> {
+    fn set_separator(&mut self, separator: Separator) { self.argument.set_separator(separator); }
+}
+
+impl<A, E, S> WithSeparator for LiteralThen<A, E, S> {
+    fn set_separator(&mut self, separator: Separator) { self.inherited.separator = Some(separator); }
+}
+
+impl<A, E, C, S> WithSeparator for LiteralThenExecutor<A, E, C, S> {
+    fn set_separator(&mut self, separator: Separator) { self.argument.set_separator(separator); }
+}
+
+impl<E1, E2> WithSeparator for ThenWrapper<E1, E2>
+where
+    E1: WithSeparator,
+    E2: WithSeparator,
+{
+    fn set_separator(&mut self, separator: Separator) {
+        self.first.set_separator(separator);
+        self.second.set_separator(separator);
+    }
+}
+
+impl<B: ?Sized> WithSeparator for Branches<B> {
+    fn set_separator(&mut self, separator: Separator) { self.inherited.separator = Some(separator); }
+}
+
+impl<E, const N: usize> WithSeparator for Choice<[E; N]>
+where
+    E: WithSeparator,
+{
+    fn set_separator(&mut self, separator: Separator) {
+        for branch in &mut self.branches {
+            branch.set_separator(separator);
+        }
+    }
+}
+
+impl<E> WithSeparator for Described<E>
+where
+    E: WithSeparator,
+{
+    fn set_separator(&mut self, separator: Separator) { self.parser.set_separator(separator); }
+}
+
+impl<S, E> WithSeparator for HelpArgument<S, E>
+where
+    E: WithSeparator,
+{
+    fn set_separator(&mut self, separator: Separator) {
+        self.separator = Some(separator);
+        self.argument.set_separator(separator);
+    }
+}
+
+impl<S, E, C> WithSeparator for HelpExecutor<S, E, C>
+where
+    E: WithSeparator,
+{
+    fn set_separator(&mut self, separator: Separator) { self.help.set_separator(separator); }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use nom::Finish;
+
+    use crate::parsers::Separator;
+    use crate::{
+        boolean, choice, integer_i32, literal, BuildExecute, BuildPropagate, Execute, ExecuteBranch, Then, ThenAll, WithSeparator,
+    };
+
+    #[test]
+    fn test_separator() {
+        let parser = literal("foo")
+            .then(integer_i32("bar").then(boolean("baz").build_propagate(|_: (), (bar, baz)| Ok::<_, Infallible>(bar + baz as i32))))
+            .then(literal("help").build_exec(|_: ()| Ok::<_, Infallible>(-1)));
+        assert_eq!(6, parser.execute((), "foo 5 true").unwrap().1);
+        let error = parser.execute((), "foo  5 true").finish().unwrap_err();
+        assert_eq!("Expected a single space between arguments", error.kind.to_string());
+        assert_eq!(4, error.cursor("foo  5 true").char);
+        let error = parser.execute((), "foo 5\ttrue").finish().unwrap_err();
+        assert_eq!("Expected a single space between arguments", error.kind.to_string());
+        let error = parser.execute((), "foo 5true").finish().unwrap_err();
+        assert_eq!("Expected whitespace to end one argument, but found trailing data", error.kind.to_string());
+
+        let parser = parser.separator(Separator::Lenient);
+        assert_eq!(5, parser.execute((), "foo \t 5   false").unwrap().1);
+        assert_eq!(-1, parser.execute((), "foo\thelp").unwrap().1);
+        assert_eq!(
+            "Expected whitespace to end one argument, but found trailing data",
+            parser.execute((), "foo 5true").finish().unwrap_err().kind.to_string()
+        );
+
+        let parser = choice((
+            literal("a").then(integer_i32("x").build_exec(|_, x| Ok::<_, Infallible>(x))),
+            literal("b").build_exec(|_| Ok::<_, Infallible>(0)),
+        ))
+        .separator(Separator::Lenient);
+        assert_eq!(3, parser.execute((), "a  3").unwrap().1);
+
+        let parser = literal("foo")
+            .then(literal("a").build_exec(|_| Ok::<_, Infallible>(0)))
+            .separator(Separator::Lenient)
+            .then(literal("b").then(integer_i32("x").build_exec(|_, x| Ok::<_, Infallible>(x))));
+        assert_eq!(4, parser.execute((), "foo  b  4").unwrap().1);
+
+        let branches: Vec<Box<dyn ExecuteBranch<(), i32>>> = vec![
+            Box::new(
+                literal("a")
+                    .then(integer_i32("x").build_exec(|_, x| Ok::<_, Infallible>(x)))
+                    .separator(Separator::Lenient),
+            ),
+            Box::new(literal("b").then(integer_i32("x").build_exec(|_, x| Ok::<_, Infallible>(x)))),
+        ];
+        let parser = literal("foo").then_all(branches).separator(Separator::Lenient);
+        assert_eq!(5, parser.execute((), "foo  a  5").unwrap().1);
+        assert_eq!(5, parser.execute((), "foo  b  5").unwrap().1);
+
+        let lenient = || {
+            literal("a")
+                .then(integer_i32("x").build_exec(|_, x| Ok::<_, Infallible>(x)))
+                .separator(Separator::Lenient)
+        };
+        assert_eq!(4, literal("foo").then(lenient()).execute((), "foo a  4").unwrap().1);
+        let parser = literal("foo").then(lenient()).separator(Separator::Strict);
+        let error = parser.execute((), "foo a  4").finish().unwrap_err();
+        assert_eq!("Expected a single space between arguments", error.kind.to_string());
+    }
+}
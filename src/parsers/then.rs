@@ -6,9 +6,16 @@ pub use literal::*;
 use nom::branch::alt;
 use nom::IResult;
 
-use crate::{Chain, CommandError, Execute, IntoMultipleUsage, MultipleUsage, Propagate};
+use crate::{
+    AsyncExecute, AsyncPropagate, BoundCommand, Chain, CommandError, CommandFuture, CommandNode, CommandTree, Execute, IntoMultipleUsage,
+    MultipleUsage, ParseCommand, ParsePropagate, Propagate, SmartUsage,
+};
 
 /// Parser wrapper that correctly tries both child parsers.
+///
+/// When both parsers fail, the error that got furthest into the input is
+/// returned. Every `then` tries one more branch, for many sibling literals
+/// [`choice`](crate::choice) jumps directly to the matching branch instead.
 pub struct ThenWrapper<E1, E2> {
     pub(crate) first: E1,
     pub(crate) second: E2,
@@ -37,6 +44,52 @@ where
     }
 }
 
+impl<E1, E2, U, S> ParseCommand<S, U> for ThenWrapper<E1, E2>
+where
+    E1: ParseCommand<S, U>,
+    E2: ParseCommand<S, U>,
+    S: Copy,
+{
+    fn parse_command<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, BoundCommand<'_, S, U>, CommandError<'a>> {
+        alt((|i| self.first.parse_command(source, i), |i| self.second.parse_command(source, i)))(input)
+    }
+}
+
+impl<E1, E2, T, U, S> ParsePropagate<S, T, U> for ThenWrapper<E1, E2>
+where
+    T: Copy,
+    S: Copy,
+    E1: ParsePropagate<S, T, U>,
+    E2: ParsePropagate<S, T, U>,
+{
+    fn parse_propagate<'a>(&self, source: S, input: &'a str, data: T) -> IResult<&'a str, BoundCommand<'_, S, U>, CommandError<'a>> {
+        alt((|i| self.first.parse_propagate(source, i, data), |i| self.second.parse_propagate(source, i, data)))(input)
+    }
+}
+
+impl<E1, E2, U, S> AsyncExecute<S, U> for ThenWrapper<E1, E2>
+where
+    E1: AsyncExecute<S, U>,
+    E2: AsyncExecute<S, U>,
+    S: Copy,
+{
+    fn execute_async<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, CommandFuture<U>, CommandError<'a>> {
+        alt((|i| self.first.execute_async(source, i), |i| self.second.execute_async(source, i)))(input)
+    }
+}
+
+impl<E1, E2, T, U, S> AsyncPropagate<S, T, U> for ThenWrapper<E1, E2>
+where
+    T: Copy,
+    S: Copy,
+    E1: AsyncPropagate<S, T, U>,
+    E2: AsyncPropagate<S, T, U>,
+{
+    fn propagate_async<'a>(&self, source: S, input: &'a str, data: T) -> IResult<&'a str, CommandFuture<U>, CommandError<'a>> {
+        alt((|i| self.first.propagate_async(source, i, data), |i| self.second.propagate_async(source, i, data)))(input)
+    }
+}
+
 impl<E1, E2> IntoMultipleUsage for ThenWrapper<E1, E2>
 where
     E1: IntoMultipleUsage,
@@ -46,3 +99,49 @@ where
 
     fn usage_gen(&self) -> Self::Item { self.first.usage_gen().chain(self.second.usage_gen()) }
 }
+
+impl<E1, E2> SmartUsage for ThenWrapper<E1, E2>
+where
+    E1: SmartUsage,
+    E2: SmartUsage,
+{
+    fn node_count(&self) -> usize { self.first.node_count() + self.second.node_count() }
+
+    fn node_usage<W: std::fmt::Write>(&self, writer: &mut W) -> Result<(), std::fmt::Error> {
+        self.first.node_usage(writer)?;
+        writer.write_char('|')?;
+        self.second.node_usage(writer)
+    }
+
+    fn smart_usage<W: std::fmt::Write>(&self, writer: &mut W, optional: bool, depth: usize) -> Result<(), std::fmt::Error> {
+        // An empty side adds no alternative, render the other one as is.
+        match (self.first.node_count(), self.second.node_count()) {
+            (_, 0) => return self.first.smart_usage(writer, optional, depth),
+            (0, _) => return self.second.smart_usage(writer, optional, depth),
+            _ => {},
+        }
+        writer.write_char(if optional {
+            '['
+        } else {
+            '('
+        })?;
+        self.node_usage(writer)?;
+        writer.write_char(if optional {
+            ']'
+        } else {
+            ')'
+        })
+    }
+}
+
+impl<E1, E2> CommandTree for ThenWrapper<E1, E2>
+where
+    E1: CommandTree,
+    E2: CommandTree,
+{
+    fn command_nodes(&self) -> Vec<CommandNode> {
+        let mut nodes = self.first.command_nodes();
+        nodes.extend(self.second.command_nodes());
+        nodes
+    }
+}
@@ -1,20 +1,28 @@
 use std::marker::PhantomData;
 
 use nom::branch::alt;
-use nom::character::complete::char;
 use nom::error::{ErrorKind, FromExternalError};
 use nom::IResult;
 
 use super::ThenWrapper;
+use crate::argument::box_task;
+use crate::bound::Bound;
+use crate::parsers::inherit::Inherited;
+use crate::parsers::separator::separated;
+use crate::tree::{executable_nodes, set_case_sensitive, then_nodes};
+use crate::usage::smart::smart_node;
 use crate::{
-    prefix, BuildExecute, BuildPropagate, Chain, ChildUsage, CmdErrorKind, CommandArgument, CommandError, Execute, IntoMultipleUsage,
-    MultipleUsage, Prefix, Propagate, TaskLogic, Then,
+    prefix, AsyncExecute, AsyncPropagate, AsyncTaskLogic, BoundCommand, BuildAsyncExecute, BuildAsyncPropagate, BuildExecute,
+    BuildPropagate, Chain, ChildUsage, CmdErrorKind, CommandArgument, CommandError, CommandFuture, CommandNode, CommandTree, Execute,
+    IntoMultipleUsage, MultipleUsage, ParseCommand, ParsePropagate, Prefix, Propagate, SingleUsage, SmartUsage, TaskLogic, Then,
 };
 
 /// Default [`Then`] implementation for any argument type.
 pub struct CommandThen<A, E, O, S> {
     pub(crate) argument: A,
     pub(crate) executor: E,
+    /// Policies passed down to the children.
+    pub(crate) inherited: Inherited,
     pub(crate) output: PhantomData<O>,
     pub(crate) source: PhantomData<S>,
 }
@@ -50,6 +58,30 @@ where
     }
 }
 
+impl<S, A, E, O, C> BuildAsyncExecute<C, ThenExecutor<A, E, C, O, S>> for CommandThen<A, E, O, S>
+where
+    C: AsyncTaskLogic<S, O>,
+{
+    fn build_async_exec(self, task: C) -> ThenExecutor<A, E, C, O, S> {
+        ThenExecutor {
+            argument: self,
+            task,
+        }
+    }
+}
+
+impl<A, E, O, C, T, S> BuildAsyncPropagate<C, T, ThenExecutor<A, E, C, O, S>> for CommandThen<A, E, O, S>
+where
+    C: AsyncTaskLogic<S, (T, O)>,
+{
+    fn build_async_propagate(self, task: C) -> ThenExecutor<A, E, C, O, S> {
+        ThenExecutor {
+            argument: self,
+            task,
+        }
+    }
+}
+
 impl<A, E, O, S> IntoMultipleUsage for CommandThen<A, E, O, S>
 where
     A: IntoMultipleUsage + ChildUsage,
@@ -77,8 +109,7 @@ where
 {
     fn execute<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, U, CommandError<'a>> {
         let (input, result) = self.argument.parse(source, input)?;
-        let (input, _) = char(' ')(input)?;
-        self.executor.propagate(source, input, result)
+        separated(self.inherited, input, |input| self.executor.propagate(source, input, result))
     }
 }
 
@@ -90,8 +121,55 @@ where
 {
     fn propagate<'a>(&self, source: S, input: &'a str, data: T) -> IResult<&'a str, U, CommandError<'a>> {
         let (input, result) = self.argument.parse(source, input)?;
-        let (input, _) = char(' ')(input)?;
-        self.executor.propagate(source, input, (data, result))
+        separated(self.inherited, input, |input| self.executor.propagate(source, input, (data, result)))
+    }
+}
+
+impl<A, O, E, U, S> ParseCommand<S, U> for CommandThen<A, E, O, S>
+where
+    A: CommandArgument<S, O>,
+    E: ParsePropagate<S, O, U>,
+    S: Copy,
+{
+    fn parse_command<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, BoundCommand<'_, S, U>, CommandError<'a>> {
+        let (input, result) = self.argument.parse(source, input)?;
+        separated(self.inherited, input, |input| self.executor.parse_propagate(source, input, result))
+    }
+}
+
+impl<A, O, E, T, U, S> ParsePropagate<S, T, U> for CommandThen<A, E, O, S>
+where
+    A: CommandArgument<S, O>,
+    E: ParsePropagate<S, (T, O), U>,
+    S: Copy,
+{
+    fn parse_propagate<'a>(&self, source: S, input: &'a str, data: T) -> IResult<&'a str, BoundCommand<'_, S, U>, CommandError<'a>> {
+        let (input, result) = self.argument.parse(source, input)?;
+        separated(self.inherited, input, |input| self.executor.parse_propagate(source, input, (data, result)))
+    }
+}
+
+impl<A, O, E, U, S> AsyncExecute<S, U> for CommandThen<A, E, O, S>
+where
+    A: CommandArgument<S, O>,
+    E: AsyncPropagate<S, O, U>,
+    S: Copy,
+{
+    fn execute_async<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, CommandFuture<U>, CommandError<'a>> {
+        let (input, result) = self.argument.parse(source, input)?;
+        separated(self.inherited, input, |input| self.executor.propagate_async(source, input, result))
+    }
+}
+
+impl<A, O, E, T, U, S> AsyncPropagate<S, T, U> for CommandThen<A, E, O, S>
+where
+    A: CommandArgument<S, O>,
+    E: AsyncPropagate<S, (T, O), U>,
+    S: Copy,
+{
+    fn propagate_async<'a>(&self, source: S, input: &'a str, data: T) -> IResult<&'a str, CommandFuture<U>, CommandError<'a>> {
+        let (input, result) = self.argument.parse(source, input)?;
+        separated(self.inherited, input, |input| self.executor.propagate_async(source, input, (data, result)))
     }
 }
 
@@ -108,6 +186,7 @@ where
                 first: self.executor,
                 second: executor,
             },
+            inherited: self.inherited,
             output: PhantomData,
             source: PhantomData,
         }
@@ -138,13 +217,16 @@ where
         alt((
             |i| {
                 let (input, result) = self.argument.parse(source, i)?;
-                let (input, _) = char(' ')(input)?;
-                self.argument.executor.propagate(source, input, result)
+                separated(self.argument.inherited, input, |input| self.argument.executor.propagate(source, input, result))
             },
             |i| {
                 let (input, result) = self.argument.parse(source, i)?;
                 if !input.is_empty() {
-                    return Err(nom::Err::Failure(CommandError::from_external_error(input, ErrorKind::IsNot, CmdErrorKind::NonEmpty)));
+                    return Err(nom::Err::Error(CommandError::from_external_error(
+                        input,
+                        ErrorKind::IsNot,
+                        CmdErrorKind::IncorrectArgument,
+                    )));
                 }
                 match self.task.run(source, result) {
                     Err(e) => Err(nom::Err::Failure(CommandError::from_external_error(input, ErrorKind::MapRes, e))),
@@ -167,13 +249,16 @@ where
         alt((
             |i| {
                 let (input, result) = self.argument.parse(source, i)?;
-                let (input, _) = char(' ')(input)?;
-                self.argument.executor.propagate(source, input, (data, result))
+                separated(self.argument.inherited, input, |input| self.argument.executor.propagate(source, input, (data, result)))
             },
             |i| {
                 let (input, result) = self.argument.parse(source, i)?;
                 if !input.is_empty() {
-                    return Err(nom::Err::Failure(CommandError::from_external_error(input, ErrorKind::IsNot, CmdErrorKind::NonEmpty)));
+                    return Err(nom::Err::Error(CommandError::from_external_error(
+                        input,
+                        ErrorKind::IsNot,
+                        CmdErrorKind::IncorrectArgument,
+                    )));
                 }
                 match self.task.run(source, (data, result)) {
                     Err(e) => Err(nom::Err::Failure(CommandError::from_external_error(input, ErrorKind::MapRes, e))),
@@ -184,6 +269,134 @@ where
     }
 }
 
+impl<A, O, E, C, U, S> ParseCommand<S, U> for ThenExecutor<A, E, C, O, S>
+where
+    A: CommandArgument<S, O>,
+    E: ParsePropagate<S, O, U>,
+    C: TaskLogic<S, O, Output = U>,
+    O: Clone + 'static,
+    S: Copy,
+{
+    fn parse_command<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, BoundCommand<'_, S, U>, CommandError<'a>> {
+        alt((
+            |i| {
+                let (input, result) = self.argument.parse(source, i)?;
+                separated(self.argument.inherited, input, |input| self.argument.executor.parse_propagate(source, input, result))
+            },
+            |i| {
+                let (input, result) = self.argument.parse(source, i)?;
+                if !input.is_empty() {
+                    return Err(nom::Err::Error(CommandError::from_external_error(
+                        input,
+                        ErrorKind::IsNot,
+                        CmdErrorKind::IncorrectArgument,
+                    )));
+                }
+                Ok((
+                    input,
+                    BoundCommand::new(Bound {
+                        task: &self.task,
+                        args: result,
+                    }),
+                ))
+            },
+        ))(input)
+    }
+}
+
+impl<A, O, E, C, T, U, S> ParsePropagate<S, T, U> for ThenExecutor<A, E, C, O, S>
+where
+    T: Copy + 'static,
+    S: Copy,
+    A: CommandArgument<S, O>,
+    E: ParsePropagate<S, (T, O), U>,
+    C: TaskLogic<S, (T, O), Output = U>,
+    O: Clone + 'static,
+{
+    fn parse_propagate<'a>(&self, source: S, input: &'a str, data: T) -> IResult<&'a str, BoundCommand<'_, S, U>, CommandError<'a>> {
+        alt((
+            |i| {
+                let (input, result) = self.argument.parse(source, i)?;
+                separated(self.argument.inherited, input, |input| self.argument.executor.parse_propagate(source, input, (data, result)))
+            },
+            |i| {
+                let (input, result) = self.argument.parse(source, i)?;
+                if !input.is_empty() {
+                    return Err(nom::Err::Error(CommandError::from_external_error(
+                        input,
+                        ErrorKind::IsNot,
+                        CmdErrorKind::IncorrectArgument,
+                    )));
+                }
+                Ok((
+                    input,
+                    BoundCommand::new(Bound {
+                        task: &self.task,
+                        args: (data, result),
+                    }),
+                ))
+            },
+        ))(input)
+    }
+}
+
+impl<A, O, E, C, U, S> AsyncExecute<S, U> for ThenExecutor<A, E, C, O, S>
+where
+    A: CommandArgument<S, O>,
+    E: AsyncPropagate<S, O, U>,
+    C: AsyncTaskLogic<S, O, Output = U>,
+    S: Copy,
+{
+    fn execute_async<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, CommandFuture<U>, CommandError<'a>> {
+        alt((
+            |i| {
+                let (input, result) = self.argument.parse(source, i)?;
+                separated(self.argument.inherited, input, |input| self.argument.executor.propagate_async(source, input, result))
+            },
+            |i| {
+                let (input, result) = self.argument.parse(source, i)?;
+                if !input.is_empty() {
+                    return Err(nom::Err::Error(CommandError::from_external_error(
+                        input,
+                        ErrorKind::IsNot,
+                        CmdErrorKind::IncorrectArgument,
+                    )));
+                }
+                Ok((input, box_task(self.task.run(source, result))))
+            },
+        ))(input)
+    }
+}
+
+impl<A, O, E, C, T, U, S> AsyncPropagate<S, T, U> for ThenExecutor<A, E, C, O, S>
+where
+    T: Copy,
+    S: Copy,
+    A: CommandArgument<S, O>,
+    E: AsyncPropagate<S, (T, O), U>,
+    C: AsyncTaskLogic<S, (T, O), Output = U>,
+{
+    fn propagate_async<'a>(&self, source: S, input: &'a str, data: T) -> IResult<&'a str, CommandFuture<U>, CommandError<'a>> {
+        alt((
+            |i| {
+                let (input, result) = self.argument.parse(source, i)?;
+                separated(self.argument.inherited, input, |input| self.argument.executor.propagate_async(source, input, (data, result)))
+            },
+            |i| {
+                let (input, result) = self.argument.parse(source, i)?;
+                if !input.is_empty() {
+                    return Err(nom::Err::Error(CommandError::from_external_error(
+                        input,
+                        ErrorKind::IsNot,
+                        CmdErrorKind::IncorrectArgument,
+                    )));
+                }
+                Ok((input, box_task(self.task.run(source, (data, result)))))
+            },
+        ))(input)
+    }
+}
+
 impl<A, E, C, O, S> IntoMultipleUsage for ThenExecutor<A, E, C, O, S>
 where
     A: IntoMultipleUsage + ChildUsage,
@@ -207,3 +420,53 @@ where
 
     fn usage_child(&self) -> Self::Child { self.argument.usage_child() }
 }
+
+impl<A, E, O, S> SmartUsage for CommandThen<A, E, O, S>
+where
+    A: ChildUsage,
+    E: SmartUsage,
+{
+    fn node_count(&self) -> usize { 1 }
+
+    fn node_usage<W: std::fmt::Write>(&self, writer: &mut W) -> Result<(), std::fmt::Error> { self.usage_child().usage(writer) }
+
+    fn smart_usage<W: std::fmt::Write>(&self, writer: &mut W, optional: bool, depth: usize) -> Result<(), std::fmt::Error> {
+        smart_node(&self.usage_child(), &self.executor, false, writer, optional, depth)
+    }
+}
+
+impl<A, E, C, O, S> SmartUsage for ThenExecutor<A, E, C, O, S>
+where
+    A: ChildUsage,
+    E: SmartUsage,
+{
+    fn node_count(&self) -> usize { 1 }
+
+    fn node_usage<W: std::fmt::Write>(&self, writer: &mut W) -> Result<(), std::fmt::Error> { self.usage_child().usage(writer) }
+
+    fn smart_usage<W: std::fmt::Write>(&self, writer: &mut W, optional: bool, depth: usize) -> Result<(), std::fmt::Error> {
+        smart_node(&self.usage_child(), &self.argument.executor, true, writer, optional, depth)
+    }
+}
+
+impl<A, E, O, S> CommandTree for CommandThen<A, E, O, S>
+where
+    A: CommandTree,
+    E: CommandTree,
+{
+    fn command_nodes(&self) -> Vec<CommandNode> {
+        let mut nodes = then_nodes(&self.argument, &self.executor);
+        if let Some(case_sensitive) = self.inherited.case_sensitive {
+            set_case_sensitive(&mut nodes, case_sensitive);
+        }
+        nodes
+    }
+}
+
+impl<A, E, C, O, S> CommandTree for ThenExecutor<A, E, C, O, S>
+where
+    A: CommandTree,
+    E: CommandTree,
+{
+    fn command_nodes(&self) -> Vec<CommandNode> { executable_nodes(&self.argument) }
+}
@@ -1,20 +1,29 @@
 use std::marker::PhantomData;
 
 use nom::branch::alt;
-use nom::character::complete::char;
 use nom::error::{ErrorKind, FromExternalError};
 use nom::IResult;
 
 use super::ThenWrapper;
+use crate::argument::box_task;
+use crate::bound::{Bound, BoundNoArgs};
+use crate::parsers::inherit::Inherited;
+use crate::parsers::separator::separated;
+use crate::tree::{executable_nodes, set_case_sensitive, then_nodes};
+use crate::usage::smart::smart_node;
 use crate::{
-    prefix, BuildExecute, BuildPropagate, Chain, ChildUsage, CmdErrorKind, CommandArgument, CommandError, Execute, IntoMultipleUsage,
-    MultipleUsage, Prefix, Propagate, TaskLogic, TaskLogicNoArgs, Then,
+    prefix, AsyncExecute, AsyncPropagate, AsyncTaskLogic, AsyncTaskLogicNoArgs, BoundCommand, BuildAsyncExecute, BuildAsyncPropagate,
+    BuildExecute, BuildPropagate, Chain, ChildUsage, CmdErrorKind, CommandArgument, CommandError, CommandFuture, CommandNode, CommandTree,
+    Execute, IntoMultipleUsage, MultipleUsage, ParseCommand, ParsePropagate, Prefix, Propagate, SingleUsage, SmartUsage, TaskLogic,
+    TaskLogicNoArgs, Then,
 };
 
 /// Default [`Then`] implementation for argument parsers that return `()`.
 pub struct LiteralThen<A, E, S> {
     pub(crate) argument: A,
     pub(crate) executor: E,
+    /// Policies passed down to the children.
+    pub(crate) inherited: Inherited,
     pub(crate) source: PhantomData<S>,
 }
 
@@ -49,6 +58,30 @@ where
     }
 }
 
+impl<A, E, C, S> BuildAsyncExecute<C, LiteralThenExecutor<A, E, C, S>> for LiteralThen<A, E, S>
+where
+    C: AsyncTaskLogicNoArgs<S>,
+{
+    fn build_async_exec(self, task: C) -> LiteralThenExecutor<A, E, C, S> {
+        LiteralThenExecutor {
+            argument: self,
+            task,
+        }
+    }
+}
+
+impl<A, E, C, T, S> BuildAsyncPropagate<C, T, LiteralThenExecutor<A, E, C, S>> for LiteralThen<A, E, S>
+where
+    C: AsyncTaskLogic<S, T>,
+{
+    fn build_async_propagate(self, task: C) -> LiteralThenExecutor<A, E, C, S> {
+        LiteralThenExecutor {
+            argument: self,
+            task,
+        }
+    }
+}
+
 impl<A, E, S> IntoMultipleUsage for LiteralThen<A, E, S>
 where
     A: IntoMultipleUsage + ChildUsage,
@@ -76,8 +109,7 @@ where
 {
     fn execute<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, U, CommandError<'a>> {
         let (input, _) = self.argument.parse(source, input)?;
-        let (input, _) = char(' ')(input)?;
-        self.executor.execute(source, input)
+        separated(self.inherited, input, |input| self.executor.execute(source, input))
     }
 }
 
@@ -89,8 +121,55 @@ where
 {
     fn propagate<'a>(&self, source: S, input: &'a str, data: T) -> IResult<&'a str, U, CommandError<'a>> {
         let (input, _) = self.argument.parse(source, input)?;
-        let (input, _) = char(' ')(input)?;
-        self.executor.propagate(source, input, data)
+        separated(self.inherited, input, |input| self.executor.propagate(source, input, data))
+    }
+}
+
+impl<A, E, U, S> ParseCommand<S, U> for LiteralThen<A, E, S>
+where
+    A: CommandArgument<S, ()>,
+    E: ParseCommand<S, U>,
+    S: Copy,
+{
+    fn parse_command<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, BoundCommand<'_, S, U>, CommandError<'a>> {
+        let (input, _) = self.argument.parse(source, input)?;
+        separated(self.inherited, input, |input| self.executor.parse_command(source, input))
+    }
+}
+
+impl<A, E, T, U, S> ParsePropagate<S, T, U> for LiteralThen<A, E, S>
+where
+    A: CommandArgument<S, ()>,
+    E: ParsePropagate<S, T, U>,
+    S: Copy,
+{
+    fn parse_propagate<'a>(&self, source: S, input: &'a str, data: T) -> IResult<&'a str, BoundCommand<'_, S, U>, CommandError<'a>> {
+        let (input, _) = self.argument.parse(source, input)?;
+        separated(self.inherited, input, |input| self.executor.parse_propagate(source, input, data))
+    }
+}
+
+impl<A, E, U, S> AsyncExecute<S, U> for LiteralThen<A, E, S>
+where
+    A: CommandArgument<S, ()>,
+    E: AsyncExecute<S, U>,
+    S: Copy,
+{
+    fn execute_async<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, CommandFuture<U>, CommandError<'a>> {
+        let (input, _) = self.argument.parse(source, input)?;
+        separated(self.inherited, input, |input| self.executor.execute_async(source, input))
+    }
+}
+
+impl<A, E, T, U, S> AsyncPropagate<S, T, U> for LiteralThen<A, E, S>
+where
+    A: CommandArgument<S, ()>,
+    E: AsyncPropagate<S, T, U>,
+    S: Copy,
+{
+    fn propagate_async<'a>(&self, source: S, input: &'a str, data: T) -> IResult<&'a str, CommandFuture<U>, CommandError<'a>> {
+        let (input, _) = self.argument.parse(source, input)?;
+        separated(self.inherited, input, |input| self.executor.propagate_async(source, input, data))
     }
 }
 
@@ -107,6 +186,7 @@ where
                 first: self.executor,
                 second: executor,
             },
+            inherited: self.inherited,
             source: PhantomData,
         }
     }
@@ -129,13 +209,16 @@ where
         alt((
             |i| {
                 let (input, _) = self.argument.parse(source, i)?;
-                let (input, _) = char(' ')(input)?;
-                self.argument.executor.execute(source, input)
+                separated(self.argument.inherited, input, |input| self.argument.executor.execute(source, input))
             },
             |i| {
                 let (input, _) = self.argument.parse(source, i)?;
                 if !input.is_empty() {
-                    return Err(nom::Err::Failure(CommandError::from_external_error(input, ErrorKind::IsNot, CmdErrorKind::NonEmpty)));
+                    return Err(nom::Err::Error(CommandError::from_external_error(
+                        input,
+                        ErrorKind::IsNot,
+                        CmdErrorKind::IncorrectArgument,
+                    )));
                 }
                 match self.task.run(source) {
                     Err(e) => Err(nom::Err::Failure(CommandError::from_external_error(input, ErrorKind::MapRes, e))),
@@ -158,13 +241,16 @@ where
         alt((
             |i| {
                 let (input, _) = self.argument.parse(source, i)?;
-                let (input, _) = char(' ')(input)?;
-                self.argument.executor.propagate(source, input, data)
+                separated(self.argument.inherited, input, |input| self.argument.executor.propagate(source, input, data))
             },
             |i| {
                 let (input, _) = self.argument.parse(source, i)?;
                 if !input.is_empty() {
-                    return Err(nom::Err::Failure(CommandError::from_external_error(input, ErrorKind::IsNot, CmdErrorKind::NonEmpty)));
+                    return Err(nom::Err::Error(CommandError::from_external_error(
+                        input,
+                        ErrorKind::IsNot,
+                        CmdErrorKind::IncorrectArgument,
+                    )));
                 }
                 match self.task.run(source, data) {
                     Err(e) => Err(nom::Err::Failure(CommandError::from_external_error(input, ErrorKind::MapRes, e))),
@@ -175,6 +261,126 @@ where
     }
 }
 
+impl<A, E, C, U, S> ParseCommand<S, U> for LiteralThenExecutor<A, E, C, S>
+where
+    A: CommandArgument<S, ()>,
+    E: ParseCommand<S, U>,
+    C: TaskLogicNoArgs<S, Output = U>,
+    S: Copy,
+{
+    fn parse_command<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, BoundCommand<'_, S, U>, CommandError<'a>> {
+        alt((
+            |i| {
+                let (input, _) = self.argument.parse(source, i)?;
+                separated(self.argument.inherited, input, |input| self.argument.executor.parse_command(source, input))
+            },
+            |i| {
+                let (input, _) = self.argument.parse(source, i)?;
+                if !input.is_empty() {
+                    return Err(nom::Err::Error(CommandError::from_external_error(
+                        input,
+                        ErrorKind::IsNot,
+                        CmdErrorKind::IncorrectArgument,
+                    )));
+                }
+                Ok((input, BoundCommand::new(BoundNoArgs { task: &self.task })))
+            },
+        ))(input)
+    }
+}
+
+impl<A, E, C, T, U, S> ParsePropagate<S, T, U> for LiteralThenExecutor<A, E, C, S>
+where
+    T: Copy + 'static,
+    S: Copy,
+    A: CommandArgument<S, ()>,
+    E: ParsePropagate<S, T, U>,
+    C: TaskLogic<S, T, Output = U>,
+{
+    fn parse_propagate<'a>(&self, source: S, input: &'a str, data: T) -> IResult<&'a str, BoundCommand<'_, S, U>, CommandError<'a>> {
+        alt((
+            |i| {
+                let (input, _) = self.argument.parse(source, i)?;
+                separated(self.argument.inherited, input, |input| self.argument.executor.parse_propagate(source, input, data))
+            },
+            |i| {
+                let (input, _) = self.argument.parse(source, i)?;
+                if !input.is_empty() {
+                    return Err(nom::Err::Error(CommandError::from_external_error(
+                        input,
+                        ErrorKind::IsNot,
+                        CmdErrorKind::IncorrectArgument,
+                    )));
+                }
+                Ok((
+                    input,
+                    BoundCommand::new(Bound {
+                        task: &self.task,
+                        args: data,
+                    }),
+                ))
+            },
+        ))(input)
+    }
+}
+
+impl<A, E, C, U, S> AsyncExecute<S, U> for LiteralThenExecutor<A, E, C, S>
+where
+    A: CommandArgument<S, ()>,
+    E: AsyncExecute<S, U>,
+    C: AsyncTaskLogicNoArgs<S, Output = U>,
+    S: Copy,
+{
+    fn execute_async<'a>(&self, source: S, input: &'a str) -> IResult<&'a str, CommandFuture<U>, CommandError<'a>> {
+        alt((
+            |i| {
+                let (input, _) = self.argument.parse(source, i)?;
+                separated(self.argument.inherited, input, |input| self.argument.executor.execute_async(source, input))
+            },
+            |i| {
+                let (input, _) = self.argument.parse(source, i)?;
+                if !input.is_empty() {
+                    return Err(nom::Err::Error(CommandError::from_external_error(
+                        input,
+                        ErrorKind::IsNot,
+                        CmdErrorKind::IncorrectArgument,
+                    )));
+                }
+                Ok((input, box_task(self.task.run(source))))
+            },
+        ))(input)
+    }
+}
+
+impl<A, E, C, T, U, S> AsyncPropagate<S, T, U> for LiteralThenExecutor<A, E, C, S>
+where
+    T: Copy,
+    S: Copy,
+    A: CommandArgument<S, ()>,
+    E: AsyncPropagate<S, T, U>,
+    C: AsyncTaskLogic<S, T, Output = U>,
+{
+    fn propagate_async<'a>(&self, source: S, input: &'a str, data: T) -> IResult<&'a str, CommandFuture<U>, CommandError<'a>> {
+        alt((
+            |i| {
+                let (input, _) = self.argument.parse(source, i)?;
+                separated(self.argument.inherited, input, |input| self.argument.executor.propagate_async(source, input, data))
+            },
+            |i| {
+                let (input, _) = self.argument.parse(source, i)?;
+                if !input.is_empty() {
+                    return Err(nom::Err::Error(CommandError::from_external_error(
+                        input,
+                        ErrorKind::IsNot,
+                        CmdErrorKind::IncorrectArgument,
+                    )));
+                }
+                Ok((input, box_task(self.task.run(source, data))))
+            },
+        ))(input)
+    }
+}
+
 impl<A, E, C, S> CommandArgument<S, ()> for LiteralThenExecutor<A, E, C, S>
 where
     A: CommandArgument<S, ()>,
@@ -205,3 +411,53 @@ where
 
     fn usage_child(&self) -> Self::Child { self.argument.usage_child() }
 }
+
+impl<A, E, S> SmartUsage for LiteralThen<A, E, S>
+where
+    A: ChildUsage,
+    E: SmartUsage,
+{
+    fn node_count(&self) -> usize { 1 }
+
+    fn node_usage<W: std::fmt::Write>(&self, writer: &mut W) -> Result<(), std::fmt::Error> { self.usage_child().usage(writer) }
+
+    fn smart_usage<W: std::fmt::Write>(&self, writer: &mut W, optional: bool, depth: usize) -> Result<(), std::fmt::Error> {
+        smart_node(&self.usage_child(), &self.executor, false, writer, optional, depth)
+    }
+}
+
+impl<A, E, C, S> SmartUsage for LiteralThenExecutor<A, E, C, S>
+where
+    A: ChildUsage,
+    E: SmartUsage,
+{
+    fn node_count(&self) -> usize { 1 }
+
+    fn node_usage<W: std::fmt::Write>(&self, writer: &mut W) -> Result<(), std::fmt::Error> { self.usage_child().usage(writer) }
+
+    fn smart_usage<W: std::fmt::Write>(&self, writer: &mut W, optional: bool, depth: usize) -> Result<(), std::fmt::Error> {
+        smart_node(&self.usage_child(), &self.argument.executor, true, writer, optional, depth)
+    }
+}
+
+impl<A, E, S> CommandTree for LiteralThen<A, E, S>
+where
+    A: CommandTree,
+    E: CommandTree,
+{
+    fn command_nodes(&self) -> Vec<CommandNode> {
+        let mut nodes = then_nodes(&self.argument, &self.executor);
+        if let Some(case_sensitive) = self.inherited.case_sensitive {
+            set_case_sensitive(&mut nodes, case_sensitive);
+        }
+        nodes
+    }
+}
+
+impl<A, E, C, S> CommandTree for LiteralThenExecutor<A, E, C, S>
+where
+    A: CommandTree,
+    E: CommandTree,
+{
+    fn command_nodes(&self) -> Vec<CommandNode> { executable_nodes(&self.argument) }
+}
@@ -0,0 +1,95 @@
+use std::ops::Range;
+
+use crate::error::context;
+use crate::translation::write_json_str;
+use crate::{CmdErrorKind, CommandError, Cursor, OwnedCommandError};
+
+const RED: &str = "\x1b[31m";
+const RED_UNDERLINE: &str = "\x1b[31;4m";
+const RESET: &str = "\x1b[0m";
+
+impl<'a> CommandError<'a> {
+    /// Render this error for a terminal using ANSI colors.
+    ///
+    /// The first line contains the message, the second line contains the
+    /// input before the error (at most `context_size` chars) followed by the
+    /// offending token in red and underlined.
+    pub fn render_ansi(&self, input: &str, context_size: usize) -> String { render_ansi(&self.kind, input, self.span(input), context_size) }
+
+    /// Render this error as a vanilla style JSON text component.
+    ///
+    /// The message is a translatable component with the English message as
+    /// fallback for keys unknown to the client, the input before the error
+    /// is gray and suggests the command up to the error when clicked, the
+    /// offending token is red and underlined.
+    pub fn render_json(&self, input: &str, context_size: usize) -> String { render_json(&self.kind, input, self.span(input), context_size) }
+}
+
+impl OwnedCommandError {
+    /// Same as [`CommandError::render_ansi`], returns `None` if the input or
+    /// the position in it is unknown.
+    pub fn render_ansi(&self, context_size: usize) -> Option<String> {
+        let input = self.located_input()?;
+        Some(render_ansi(&self.kind, input, self.span.clone(), context_size))
+    }
+
+    /// Same as [`CommandError::render_json`], returns `None` if the input or
+    /// the position in it is unknown.
+    pub fn render_json(&self, context_size: usize) -> Option<String> {
+        let input = self.located_input()?;
+        Some(render_json(&self.kind, input, self.span.clone(), context_size))
+    }
+}
+
+fn render_ansi(kind: &CmdErrorKind, input: &str, span: Range<Cursor>, context_size: usize) -> String {
+    let (prefix, before) = context(input, span.start.byte, context_size);
+    format!(
+        "{RED}{}{RESET}\n{}{}{RED_UNDERLINE}{}{RESET}{}",
+        kind,
+        prefix,
+        before,
+        &input[span.start.byte..span.end.byte],
+        &input[span.end.byte..]
+    )
+}
+
+fn render_json(kind: &CmdErrorKind, input: &str, span: Range<Cursor>, context_size: usize) -> String {
+    let (prefix, before) = context(input, span.start.byte, context_size);
+    let mut json = String::from("{\"text\":\"\",\"color\":\"red\",\"extra\":[");
+    json.push_str(&kind.translation().to_json_with_fallback(Some(&kind.to_string())));
+    json.push_str(",\"\\n\",{\"text\":\"\",\"color\":\"gray\",\"clickEvent\":{\"action\":\"suggest_command\",\"value\":");
+    write_json_str(&mut json, &input[..span.start.byte]);
+    json.push_str("},\"extra\":[");
+    write_json_str(&mut json, &format!("{}{}", prefix, before));
+    json.push_str(",{\"text\":");
+    write_json_str(&mut json, &input[span.start.byte..span.end.byte]);
+    json.push_str(",\"color\":\"red\",\"underlined\":true},{\"translate\":\"command.context.here\",\"color\":\"red\",\"italic\":true}]}]}");
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use nom::Finish;
+
+    use crate::{integer_i32, literal, BuildExecute, Execute, Then};
+
+    #[test]
+    fn test_render() {
+        let parser = literal("foo").then(integer_i32("bar").max(10).build_exec(|_: (), _| Ok::<(), Infallible>(())));
+        let input = "/foo 13";
+        let error = parser.execute((), &input[1..]).finish().unwrap_err();
+
+        assert_eq!("\x1b[31mInteger must not be more than 10, found 13\x1b[0m\n/foo \x1b[31;4m13\x1b[0m", error.render_ansi(input, 10));
+        assert_eq!(
+            concat!(
+                r#"{"text":"","color":"red","extra":[{"translate":"argument.integer.big","#,
+                r#""fallback":"Integer must not be more than 10, found 13","with":["10","13"]},"\n","#,
+                r#"{"text":"","color":"gray","clickEvent":{"action":"suggest_command","value":"/foo "},"extra":["...oo ","#,
+                r#"{"text":"13","color":"red","underlined":true},{"translate":"command.context.here","color":"red","italic":true}]}]}"#
+            ),
+            error.render_json(input, 3)
+        );
+    }
+}
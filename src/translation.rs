@@ -0,0 +1,240 @@
+use std::borrow::{Borrow, Cow};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::hash::{BuildHasher, Hash};
+
+use crate::error::NumberKind;
+use crate::{CmdErrorKind, HelpEntry};
+
+/// Translation key together with its arguments.
+///
+/// Built-in errors use the same keys as the vanilla language files where
+/// possible (e.g. `argument.integer.big`), errors unknown to vanilla use keys
+/// prefixed with `brigadier_rs.`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Translatable {
+    pub key: &'static str,
+    pub args: Vec<String>,
+}
+
+impl Translatable {
+    /// Translate using `translator`, returns `None` if the key is unknown.
+    pub fn translate<T: Translator + ?Sized>(&self, translator: &T) -> Option<String> {
+        let template = translator.template(self.key)?;
+        Some(format_template(&template, &self.args))
+    }
+
+    /// Returns this translation as a vanilla translatable chat component.
+    pub fn to_json(&self) -> String { self.to_json_with_fallback(None) }
+
+    /// Same as [`to_json`](Translatable::to_json), clients that do not know
+    /// the key show `fallback` instead.
+    pub(crate) fn to_json_with_fallback(&self, fallback: Option<&str>) -> String {
+        let mut json = String::from("{\"translate\":");
+        write_json_str(&mut json, self.key);
+        if let Some(fallback) = fallback {
+            json.push_str(",\"fallback\":");
+            write_json_str(&mut json, fallback);
+        }
+        json.push_str(",\"with\":[");
+        for (i, arg) in self.args.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            write_json_str(&mut json, arg);
+        }
+        json.push_str("]}");
+        json
+    }
+}
+
+/// Source of translated message templates.
+///
+/// Templates use the same format as the vanilla language files, arguments are
+/// inserted at `%s` (in order) or `%1$s` (by position) and `%%` is a literal
+/// `%`.
+pub trait Translator {
+    /// Returns the template for `key`, `None` if the key is unknown.
+    fn template(&self, key: &str) -> Option<Cow<'_, str>>;
+}
+
+impl<T: Translator + ?Sized> Translator for &T {
+    fn template(&self, key: &str) -> Option<Cow<'_, str>> { (**self).template(key) }
+}
+
+impl<K, V, H> Translator for HashMap<K, V, H>
+where
+    K: Borrow<str> + Hash + Eq,
+    V: AsRef<str>,
+    H: BuildHasher,
+{
+    fn template(&self, key: &str) -> Option<Cow<'_, str>> { self.get(key).map(|v| Cow::Borrowed(v.as_ref())) }
+}
+
+impl CmdErrorKind {
+    /// Translation key and arguments of this error.
+    pub fn translation(&self) -> Translatable {
+        let (key, args) = match self {
+            CmdErrorKind::Char(c) => ("brigadier_rs.expected.char", vec![c.to_string()]),
+            CmdErrorKind::External(e) => ("brigadier_rs.external", vec![e.root_cause().to_string()]),
+            CmdErrorKind::Nom(e) => ("brigadier_rs.parse", vec![e.description().to_owned()]),
+            CmdErrorKind::ExpectedLiteral(literal) => ("argument.literal.incorrect", vec![literal.to_string()]),
+            CmdErrorKind::UnknownLiteral { expected, .. } => ("argument.literal.incorrect", vec![expected.to_string()]),
+            CmdErrorKind::ExpectedNumber(number) => (
+                match number {
+                    NumberKind::Integer => "parsing.int.expected",
+                    NumberKind::Long => "parsing.long.expected",
+                    NumberKind::Float => "parsing.float.expected",
+                    NumberKind::Double => "parsing.double.expected",
+                },
+                vec![],
+            ),
+            CmdErrorKind::InvalidNumber { number, found } => (
+                match number {
+                    NumberKind::Integer => "parsing.int.invalid",
+                    NumberKind::Long => "parsing.long.invalid",
+                    NumberKind::Float => "parsing.float.invalid",
+                    NumberKind::Double => "parsing.double.invalid",
+                },
+                vec![found.clone()],
+            ),
+            CmdErrorKind::TooLow { number, min, found } => (
+                match number {
+                    NumberKind::Integer => "argument.integer.low",
+                    NumberKind::Long => "argument.long.low",
+                    NumberKind::Float => "argument.float.low",
+                    NumberKind::Double => "argument.double.low",
+                },
+                vec![min.clone(), found.clone()],
+            ),
+            CmdErrorKind::TooHigh { number, max, found } => (
+                match number {
+                    NumberKind::Integer => "argument.integer.big",
+                    NumberKind::Long => "argument.long.big",
+                    NumberKind::Float => "argument.float.big",
+                    NumberKind::Double => "argument.double.big",
+                },
+                vec![max.clone(), found.clone()],
+            ),
+            CmdErrorKind::ExpectedBool => ("parsing.bool.expected", vec![]),
+            CmdErrorKind::InvalidBool(found) => ("parsing.bool.invalid", vec![found.clone()]),
+            CmdErrorKind::InvalidEscape(c) => ("parsing.quote.escape", vec![c.to_string()]),
+            CmdErrorKind::UnclosedQuote => ("parsing.quote.expected.end", vec![]),
+            CmdErrorKind::UnknownCommand => ("command.unknown.command", vec![]),
+            CmdErrorKind::IncorrectArgument => ("command.unknown.argument", vec![]),
+            CmdErrorKind::IncompleteCommand => ("brigadier_rs.command.incomplete", vec![]),
+            CmdErrorKind::ExpectedSeparator => ("command.expected.separator", vec![]),
+            CmdErrorKind::UnexpectedWhitespace => ("brigadier_rs.separator.single", vec![]),
+            CmdErrorKind::ExpectedOneOf(expected) => {
+                let mut list = String::new();
+                for (i, e) in expected.iter().enumerate() {
+                    if i > 0 {
+                        list.push_str(", ");
+                    }
+                    let _ = write!(list, "{}", e);
+                }
+                ("brigadier_rs.expected.one_of", vec![list])
+            },
+        };
+        Translatable { key, args }
+    }
+
+    /// Translate this error using `translator`, falling back to the English
+    /// message if the key is unknown.
+    pub fn translate<T: Translator + ?Sized>(&self, translator: &T) -> String {
+        self.translation().translate(translator).unwrap_or_else(|| self.to_string())
+    }
+}
+
+impl HelpEntry {
+    /// Translate the description using `translator`, the description is used
+    /// as translation key. Falls back to the description itself if the key is
+    /// unknown.
+    pub fn translate_description<T: Translator + ?Sized>(&self, translator: &T) -> Cow<'_, str> {
+        match translator.template(&self.description) {
+            Some(description) => Cow::Owned(format_template(&description, &[])),
+            None => Cow::Borrowed(&self.description),
+        }
+    }
+}
+
+/// Fill in the arguments of a language file template.
+pub(crate) fn format_template(template: &str, args: &[String]) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut next = 0;
+    let mut rest = template;
+    while let Some(i) = rest.find('%') {
+        result.push_str(&rest[..i]);
+        rest = &rest[i + 1..];
+        if let Some(r) = rest.strip_prefix('%') {
+            result.push('%');
+            rest = r;
+        } else if let Some(r) = rest.strip_prefix('s') {
+            result.push_str(args.get(next).map_or("", String::as_str));
+            next += 1;
+            rest = r;
+        } else {
+            let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+            match (rest[..digits].parse::<usize>(), rest[digits..].strip_prefix("$s")) {
+                (Ok(position), Some(r)) => {
+                    result.push_str(args.get(position.wrapping_sub(1)).map_or("", String::as_str));
+                    rest = r;
+                },
+                _ => result.push('%'),
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Write `value` as a quoted and escaped JSON string.
+pub(crate) fn write_json_str(json: &mut String, value: &str) {
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            },
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::convert::Infallible;
+
+    use nom::Finish;
+
+    use crate::parsers::help::ThenHelp;
+    use crate::{integer_i32, literal, BuildExecute, Execute, HelpUsage, Then};
+
+    #[test]
+    fn test_translation() {
+        let parser = literal("foo")
+            .then(integer_i32("bar").max(10).build_exec(|_: (), _| Ok::<(), Infallible>(())))
+            .help("commands.foo.description")
+            .build_exec(|_: (), _| Ok::<(), Infallible>(()));
+        let mut translator = HashMap::new();
+        translator.insert("argument.integer.big", "Ganzzahl darf nicht größer als %s sein, %s gefunden");
+        translator.insert("commands.foo.description", "Beschreibung von %%foo");
+
+        let error = parser.execute((), "foo 13").finish().unwrap_err();
+        assert_eq!("Ganzzahl darf nicht größer als 10 sein, 13 gefunden", error.kind.translate(&translator));
+        assert_eq!(r#"{"translate":"argument.integer.big","with":["10","13"]}"#, error.kind.translation().to_json());
+        let error = parser.execute((), "bar").finish().unwrap_err();
+        assert_eq!("Unknown literal 'bar', expected foo", error.kind.translate(&translator));
+        let error = parser.execute((), "foo 1 x").finish().unwrap_err();
+        assert_eq!("Incorrect argument for command", error.kind.translate(&translator));
+        assert_eq!("command.unknown.argument", error.kind.translation().key);
+        assert_eq!("Beschreibung von %foo", parser.help().translate_description(&translator));
+    }
+}
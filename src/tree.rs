@@ -0,0 +1,238 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+/// Node in the structure of a parser, see [`CommandTree`].
+///
+/// With the `serde` feature enabled this type can be (de)serialized, e.g. to
+/// JSON:
+/// ```json
+/// {"type": "argument", "name": "bar", "parser": "brigadier:integer", "properties": {"min": 0}, "executable": true, "children": []}
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CommandNode {
+    /// Literal or argument name.
+    pub name: Cow<'static, str>,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub kind: NodeKind,
+    /// Whether the command can be executed at this node.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub executable: bool,
+    /// Whether a literal node only matches its exact name.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "std::ops::Not::not"))]
+    pub case_sensitive: bool,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub description: Option<Cow<'static, str>>,
+    /// Requirement (e.g. a permission) needed to use this node, see
+    /// [`Describe::requires`](crate::Describe::requires).
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub requirement: Option<Cow<'static, str>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub children: Vec<CommandNode>,
+}
+
+/// Literal or argument node.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "snake_case"))]
+pub enum NodeKind {
+    Literal,
+    Argument {
+        /// Argument type, using the vanilla parser names (e.g.
+        /// `brigadier:integer`).
+        parser: Cow<'static, str>,
+        #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "BTreeMap::is_empty"))]
+        properties: BTreeMap<Cow<'static, str>, NodeProperty>,
+    },
+}
+
+/// Property of an argument node, e.g. the bounds of a number.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum NodeProperty {
+    Integer(i64),
+    Unsigned(u64),
+    Float(f64),
+}
+
+macro_rules! impl_property {
+    ($($ty:ty => $variant:ident),*) => {
+        $(impl From<$ty> for NodeProperty {
+            fn from(value: $ty) -> Self { NodeProperty::$variant(value.into()) }
+        })*
+    };
+}
+
+impl_property!(
+    i8 => Integer, i16 => Integer, i32 => Integer, i64 => Integer,
+    u8 => Integer, u16 => Integer, u32 => Integer,
+    f32 => Float, f64 => Float
+);
+
+impl From<u64> for NodeProperty {
+    /// Only values above `i64::MAX` are stored as [`NodeProperty::Unsigned`].
+    fn from(value: u64) -> Self { i64::try_from(value).map_or(NodeProperty::Unsigned(value), NodeProperty::Integer) }
+}
+
+impl CommandNode {
+    /// Create a literal node without children.
+    pub fn literal(name: impl Into<Cow<'static, str>>) -> Self { Self::new(name, NodeKind::Literal) }
+
+    /// Create an argument node of type `parser` without children.
+    pub fn argument(name: impl Into<Cow<'static, str>>, parser: impl Into<Cow<'static, str>>) -> Self {
+        Self::new(name, NodeKind::Argument {
+            parser: parser.into(),
+            properties: BTreeMap::new(),
+        })
+    }
+
+    fn new(name: impl Into<Cow<'static, str>>, kind: NodeKind) -> Self {
+        CommandNode {
+            name: name.into(),
+            kind,
+            executable: false,
+            case_sensitive: false,
+            description: None,
+            requirement: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// Add a property to an argument node, this does nothing for literals.
+    pub fn with_property(mut self, key: impl Into<Cow<'static, str>>, value: impl Into<NodeProperty>) -> Self {
+        if let NodeKind::Argument { properties, .. } = &mut self.kind {
+            properties.insert(key.into(), value.into());
+        }
+        self
+    }
+}
+
+/// Type that can describe its structure as a list of sibling
+/// [`CommandNode`]s.
+pub trait CommandTree {
+    /// Returns the top-level nodes of this parser.
+    fn command_nodes(&self) -> Vec<CommandNode>;
+}
+
+/// Nodes of `argument` followed by the nodes of `executor` as children.
+pub(crate) fn then_nodes<A, E>(argument: &A, executor: &E) -> Vec<CommandNode>
+where
+    A: CommandTree + ?Sized,
+    E: CommandTree + ?Sized,
+{
+    let children = executor.command_nodes();
+    argument
+        .command_nodes()
+        .into_iter()
+        .map(|mut node| {
+            node.children.extend(children.iter().cloned());
+            node
+        })
+        .collect()
+}
+
+/// Set `case_sensitive` on all literal nodes in `nodes` and below.
+pub(crate) fn set_case_sensitive(nodes: &mut [CommandNode], case_sensitive: bool) {
+    for node in nodes {
+        if matches!(node.kind, NodeKind::Literal) {
+            node.case_sensitive = case_sensitive;
+        }
+        set_case_sensitive(&mut node.children, case_sensitive);
+    }
+}
+
+/// Nodes of `parser` marked as executable.
+pub(crate) fn executable_nodes<P>(parser: &P) -> Vec<CommandNode>
+where
+    P: CommandTree + ?Sized,
+{
+    parser
+        .command_nodes()
+        .into_iter()
+        .map(|mut node| {
+            node.executable = true;
+            node
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use nom::Finish;
+
+    use crate::parsers::help::ThenHelp;
+    use crate::{
+        integer_i32, integer_u64, integer_u8, literal, BuildExecute, CommandArgument, CommandNode, CommandTree, Describe, Execute,
+        NodeKind, NodeProperty, Then, UsagePrint,
+    };
+
+    #[test]
+    fn test_command_tree() {
+        let parser = literal("foo")
+            .then(
+                integer_i32("bar")
+                    .min(0)
+                    .build_exec(|_: (), _| Ok::<_, Infallible>(()))
+                    .describe("Set bar")
+                    .requires("foo.bar"),
+            )
+            .build_exec(|_: ()| Ok::<_, Infallible>(()))
+            .help("Manage foo")
+            .build_exec(|_: (), _: UsagePrint<_>| Ok::<_, Infallible>(()));
+        parser.execute((), "foo 1").unwrap();
+
+        let nodes = parser.command_nodes();
+        assert_eq!(1, nodes.len());
+        assert_eq!("foo", nodes[0].name);
+        assert!(nodes[0].executable);
+        assert_eq!(Some("Manage foo".into()), nodes[0].description);
+        let bar = &nodes[0].children[0];
+        assert_eq!(
+            NodeKind::Argument {
+                parser: "brigadier:integer".into(),
+                properties: [("min".into(), NodeProperty::Integer(0))].into(),
+            },
+            bar.kind
+        );
+        assert_eq!(Some("Set bar".into()), bar.description);
+        assert_eq!(Some("foo.bar".into()), bar.requirement);
+        assert_eq!(None, nodes[0].requirement);
+        assert_eq!("help", nodes[0].children[1].name);
+
+        let parser = literal("foo")
+            .then(literal("bar").build_exec(|_: ()| Ok::<_, Infallible>(())))
+            .help("Manage foo")
+            .build_exec(|_: (), _: UsagePrint<_>| Ok::<_, Infallible>(()));
+        let nodes = parser.command_nodes();
+        assert_eq!(vec!["bar", "help"], nodes[0].children.iter().map(|n| &n.name).collect::<Vec<_>>());
+        assert_eq!("help", nodes[0].children[0].children[0].name);
+        let properties = |node: CommandNode| match node.kind {
+            NodeKind::Argument { properties, .. } => properties,
+            NodeKind::Literal => unreachable!(),
+        };
+        let u8_properties = properties(integer_u8::<()>("x").command_nodes().remove(0));
+        assert_eq!(Some(&NodeProperty::Integer(0)), u8_properties.get("min"));
+        assert_eq!(Some(&NodeProperty::Integer(255)), u8_properties.get("max"));
+        assert_eq!(1, properties(integer_i32::<()>("x").max(5).command_nodes().remove(0)).len());
+        let u64_properties = properties(integer_u64::<()>("x").command_nodes().remove(0));
+        assert_eq!(1, u64_properties.len());
+        assert_eq!(
+            "Long must not be more than 9223372036854775807, found 9223372036854775808",
+            integer_u64::<()>("x")
+                .parse((), "9223372036854775808")
+                .finish()
+                .unwrap_err()
+                .kind
+                .to_string()
+        );
+
+        #[cfg(feature = "serde")]
+        assert_eq!(
+            r#"{"name":"bar","type":"argument","parser":"brigadier:integer","properties":{"min":0},"executable":true,"description":"Set bar","requirement":"foo.bar","children":[]}"#,
+            serde_json::to_string(bar).unwrap()
+        );
+    }
+}
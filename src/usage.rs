@@ -1,19 +1,31 @@
+use std::borrow::Cow;
 use std::fmt::{Error, Write};
 
 pub(crate) mod chain;
 pub(crate) mod combine;
+pub(crate) mod described;
 pub(crate) mod display;
+pub(crate) mod erased;
 pub(crate) mod prefix;
+pub(crate) mod smart;
 
 pub use chain::*;
-pub use combine::*;
-pub use display::UsagePrint;
+pub use described::DescribedUsage;
+pub use display::{DescribedUsagePrint, UsagePrint};
+pub use erased::*;
 pub use prefix::*;
+pub use smart::SmartUsage;
 
 /// A single usage able to write itself to a [`Write`](std::fmt::Write).
 pub trait SingleUsage {
     /// Write self to the provided writer.
     fn usage<W: Write>(&self, writer: &mut W) -> Result<(), Error>;
+
+    /// Write self to the provided writer, including argument types and
+    /// constraints.
+    ///
+    /// Defaults to [`usage`](SingleUsage::usage).
+    fn usage_typed<W: Write>(&self, writer: &mut W) -> Result<(), Error> { self.usage(writer) }
 }
 
 /// One or more usages that can be iterated through.
@@ -26,9 +38,18 @@ pub trait MultipleUsage {
     /// can be returned by writing to the writer.
     fn usage_next<W: Write>(&mut self, writer: &mut W) -> Option<Result<(), Error>>;
 
+    /// Same as [`usage_next`](MultipleUsage::usage_next), including argument
+    /// types and constraints.
+    ///
+    /// Defaults to [`usage_next`](MultipleUsage::usage_next).
+    fn usage_next_typed<W: Write>(&mut self, writer: &mut W) -> Option<Result<(), Error>> { self.usage_next(writer) }
+
     /// Returns true if there are usages left, otherwise returns false.
     fn is_next(&self) -> bool;
 
+    /// Returns the description of the next usage, if any.
+    fn usage_description(&self) -> Option<&'static str> { None }
+
     /// Chains this `MultipleUsage` with another MultipleUsage.
     fn chain<U2>(self, other: U2) -> Chain<Self, U2>
     where
@@ -48,7 +69,11 @@ pub trait MultipleUsage {
     where
         Self: Sized,
     {
-        UsagePrint { usage: self }
+        UsagePrint {
+            usage: self,
+            typed: false,
+            path: Vec::new(),
+        }
     }
 }
 
@@ -72,7 +97,11 @@ pub trait IntoMultipleUsage {
     fn usage_gen(&self) -> Self::Item;
 }
 
-impl<'a> SingleUsage for &'a str {
+impl SingleUsage for &str {
+    fn usage<W: Write>(&self, writer: &mut W) -> Result<(), Error> { writer.write_str(self) }
+}
+
+impl SingleUsage for Cow<'static, str> {
     fn usage<W: Write>(&self, writer: &mut W) -> Result<(), Error> { writer.write_str(self) }
 }
 
@@ -86,6 +115,13 @@ where
         }
         Ok(())
     }
+
+    fn usage_typed<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        if let Some(usage) = self {
+            usage.usage_typed(writer)?;
+        }
+        Ok(())
+    }
 }
 
 impl<I, U> MultipleUsage for I
@@ -98,6 +134,11 @@ where
         Some(next.usage(writer))
     }
 
+    fn usage_next_typed<W: Write>(&mut self, writer: &mut W) -> Option<Result<(), Error>> {
+        let next = self.next()?;
+        Some(next.usage_typed(writer))
+    }
+
     fn is_next(&self) -> bool { self.len() > 0 }
 }
 
@@ -109,3 +150,26 @@ where
 
     fn usage_gen(&self) -> Self::Item { std::iter::once(self.clone()) }
 }
+
+/// Usage of an argument with a type and optional constraint.
+///
+/// Written as `<name>`, or as `<name: type constraint>` when including types
+/// (e.g. `<bar: i32 0..10>`).
+#[derive(Debug, Clone)]
+pub struct ArgumentUsage {
+    pub name: &'static str,
+    pub type_name: &'static str,
+    pub constraint: Option<String>,
+}
+
+impl SingleUsage for ArgumentUsage {
+    fn usage<W: Write>(&self, writer: &mut W) -> Result<(), Error> { write!(writer, "<{}>", self.name) }
+
+    fn usage_typed<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        write!(writer, "<{}: {}", self.name, self.type_name)?;
+        if let Some(constraint) = &self.constraint {
+            write!(writer, " {}", constraint)?;
+        }
+        writer.write_char('>')
+    }
+}
@@ -22,5 +22,20 @@ where
         }
     }
 
+    fn usage_next_typed<W: std::fmt::Write>(&mut self, writer: &mut W) -> Option<Result<(), std::fmt::Error>> {
+        match self.left.usage_next_typed(writer) {
+            Some(result) => Some(result),
+            None => self.right.usage_next_typed(writer),
+        }
+    }
+
     fn is_next(&self) -> bool { self.left.is_next() || self.right.is_next() }
+
+    fn usage_description(&self) -> Option<&'static str> {
+        if self.left.is_next() {
+            self.left.usage_description()
+        } else {
+            self.right.usage_description()
+        }
+    }
 }
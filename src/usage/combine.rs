@@ -12,6 +12,13 @@ where
         }
         Ok(())
     }
+
+    fn usage_typed<W: Write>(&self, writer: &mut W) -> Result<(), std::fmt::Error> {
+        for u in self {
+            u.usage_typed(writer)?;
+        }
+        Ok(())
+    }
 }
 
 macro_rules! tuple_impls {
@@ -31,6 +38,17 @@ macro_rules! tuple_impls {
                     )+
                     Ok(())
                 }
+
+                #[inline]
+                fn usage_typed<W>(&self, writer: &mut W) -> Result<(), std::fmt::Error>
+                where
+                    W: Write,
+                {
+                    $(
+                        self.$n.usage_typed(writer)?;
+                    )+
+                    Ok(())
+                }
             }
         )+
     }
@@ -0,0 +1,26 @@
+use std::fmt::{Error, Write};
+
+use crate::MultipleUsage;
+
+/// `MultipleUsage` iterator with a description.
+///
+/// The description applies to every contained usage that has no description
+/// of its own.
+#[derive(Debug, Clone, Copy)]
+pub struct DescribedUsage<U> {
+    pub(crate) usage: U,
+    pub(crate) description: Option<&'static str>,
+}
+
+impl<U> MultipleUsage for DescribedUsage<U>
+where
+    U: MultipleUsage,
+{
+    fn usage_next<W: Write>(&mut self, writer: &mut W) -> Option<Result<(), Error>> { self.usage.usage_next(writer) }
+
+    fn usage_next_typed<W: Write>(&mut self, writer: &mut W) -> Option<Result<(), Error>> { self.usage.usage_next_typed(writer) }
+
+    fn is_next(&self) -> bool { self.usage.is_next() }
+
+    fn usage_description(&self) -> Option<&'static str> { self.usage.usage_description().or(self.description) }
+}
@@ -5,8 +5,33 @@ use crate::MultipleUsage;
 /// Wrapper of `MultipleUsage` iterator.
 ///
 /// This iterator produces [`String`]s.
+#[derive(Debug, Clone)]
 pub struct UsagePrint<U> {
     pub(crate) usage: U,
+    pub(crate) typed: bool,
+    pub(crate) path: Vec<String>,
+}
+
+impl<U> UsagePrint<U> {
+    /// Include argument types and constraints in the produced usages, e.g.
+    /// `foo <bar: i32 0..10>`.
+    pub fn typed(mut self) -> Self {
+        self.typed = true;
+        self
+    }
+
+    /// Only produce the usages below the literal `path` (excluding the root
+    /// literal), e.g. `["bar"]` keeps `foo bar <baz>` but skips `foo <qux>`.
+    ///
+    /// Literals are compared exactly, as written in the usage.
+    pub fn below<P: Into<String>>(mut self, path: impl IntoIterator<Item = P>) -> Self {
+        self.path = path.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Produce every usage together with its description, see
+    /// [`Describe`](crate::Describe).
+    pub fn with_descriptions(self) -> DescribedUsagePrint<U> { DescribedUsagePrint { print: self } }
 }
 
 impl<U> Iterator for UsagePrint<U>
@@ -16,10 +41,87 @@ where
     type Item = Result<String, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.next_any()? {
+                Ok(usage) if !usage_below(&usage, &self.path) => continue,
+                next => return Some(next),
+            }
+        }
+    }
+}
+
+impl<U> UsagePrint<U>
+where
+    U: MultipleUsage,
+{
+    /// Next usage, regardless of `path`.
+    fn next_any(&mut self) -> Option<Result<String, Error>> {
         let mut result = String::new();
-        Some(match self.usage.usage_next(&mut result)? {
+        let next = if self.typed {
+            self.usage.usage_next_typed(&mut result)
+        } else {
+            self.usage.usage_next(&mut result)
+        };
+        Some(match next? {
             Ok(_) => Ok(result),
             Err(e) => Err(e),
         })
     }
 }
+
+/// Returns true if the literals of `usage` after its root start with `path`.
+pub(crate) fn usage_below<P: AsRef<str>>(usage: &str, path: &[P]) -> bool {
+    let mut words = usage.split(' ').skip(1);
+    path.iter().all(|p| words.next() == Some(p.as_ref()))
+}
+
+/// Wrapper of [`UsagePrint`] that also produces descriptions.
+///
+/// This iterator produces [`String`]s and their optional description.
+#[derive(Debug, Clone)]
+pub struct DescribedUsagePrint<U> {
+    print: UsagePrint<U>,
+}
+
+impl<U> Iterator for DescribedUsagePrint<U>
+where
+    U: MultipleUsage,
+{
+    type Item = Result<(String, Option<&'static str>), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let description = self.print.usage.usage_description();
+            match self.print.next_any()? {
+                Ok(usage) if !usage_below(&usage, &self.print.path) => continue,
+                next => return Some(next.map(|usage| (usage, description))),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use crate::parsers::help::ThenHelp;
+    use crate::{boolean, float_64, integer_i32, literal, BuildExecute, Execute, Then, UsagePrint};
+
+    #[test]
+    fn test_typed_usage() {
+        let parser = literal("foo")
+            .then(
+                integer_i32("bar")
+                    .min(0)
+                    .max(10)
+                    .build_exec(|_: (), _| Ok::<Vec<String>, Infallible>(vec![])),
+            )
+            .then(boolean("flag").build_exec(|_: (), _| Ok::<Vec<String>, Infallible>(vec![])))
+            .then(float_64("baz").max(0.5).build_exec(|_: (), _| Ok::<Vec<String>, Infallible>(vec![])))
+            .help("Test description")
+            .build_exec(|_: (), usages: UsagePrint<_>| Ok::<_, Infallible>(usages.typed().collect::<Result<Vec<_>, _>>().unwrap()));
+
+        let (_, usages) = parser.execute((), "foo help").unwrap();
+        assert_eq!(vec!["foo <bar: i32 0..10>", "foo <flag: bool>", "foo <baz: f64 ..0.5>"], usages);
+    }
+}
@@ -0,0 +1,134 @@
+use std::fmt::{Error, Write};
+
+use crate::{IntoMultipleUsage, MultipleUsage, SmartUsage};
+
+/// Object safe version of [`MultipleUsage`].
+///
+/// This is implemented for every cloneable `MultipleUsage`, see
+/// [`BoxedUsage`].
+pub trait DynMultipleUsage {
+    /// See [`MultipleUsage::usage_next`] and
+    /// [`MultipleUsage::usage_next_typed`].
+    fn dyn_usage_next(&mut self, writer: &mut dyn Write, typed: bool) -> Option<Result<(), Error>>;
+
+    /// See [`MultipleUsage::is_next`].
+    fn dyn_is_next(&self) -> bool;
+
+    /// See [`MultipleUsage::usage_description`].
+    fn dyn_usage_description(&self) -> Option<&'static str>;
+
+    /// Clone into a new box.
+    fn dyn_clone(&self) -> Box<dyn DynMultipleUsage>;
+}
+
+impl<U> DynMultipleUsage for U
+where
+    U: MultipleUsage + Clone + 'static,
+{
+    fn dyn_usage_next(&mut self, mut writer: &mut dyn Write, typed: bool) -> Option<Result<(), Error>> {
+        if typed {
+            self.usage_next_typed(&mut writer)
+        } else {
+            self.usage_next(&mut writer)
+        }
+    }
+
+    fn dyn_is_next(&self) -> bool { self.is_next() }
+
+    fn dyn_usage_description(&self) -> Option<&'static str> { self.usage_description() }
+
+    fn dyn_clone(&self) -> Box<dyn DynMultipleUsage> { Box::new(self.clone()) }
+}
+
+/// Boxed `MultipleUsage` iterator of any type.
+pub struct BoxedUsage(Box<dyn DynMultipleUsage>);
+
+impl BoxedUsage {
+    /// Box `usage`.
+    pub fn new<U: MultipleUsage + Clone + 'static>(usage: U) -> Self { BoxedUsage(Box::new(usage)) }
+}
+
+impl Clone for BoxedUsage {
+    fn clone(&self) -> Self { BoxedUsage(self.0.dyn_clone()) }
+}
+
+impl std::fmt::Debug for BoxedUsage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { f.write_str("BoxedUsage(..)") }
+}
+
+impl MultipleUsage for BoxedUsage {
+    fn usage_next<W: Write>(&mut self, writer: &mut W) -> Option<Result<(), Error>> { self.0.dyn_usage_next(writer, false) }
+
+    fn usage_next_typed<W: Write>(&mut self, writer: &mut W) -> Option<Result<(), Error>> { self.0.dyn_usage_next(writer, true) }
+
+    fn is_next(&self) -> bool { self.0.dyn_is_next() }
+
+    fn usage_description(&self) -> Option<&'static str> { self.0.dyn_usage_description() }
+}
+
+/// Object safe version of [`IntoMultipleUsage`].
+pub trait DynIntoMultipleUsage {
+    /// See [`IntoMultipleUsage::usage_gen`].
+    fn dyn_usage_gen(&self) -> BoxedUsage;
+}
+
+impl<T> DynIntoMultipleUsage for T
+where
+    T: IntoMultipleUsage,
+    T::Item: Clone + 'static,
+{
+    fn dyn_usage_gen(&self) -> BoxedUsage { BoxedUsage::new(self.usage_gen()) }
+}
+
+/// Object safe version of [`SmartUsage`].
+pub trait DynSmartUsage {
+    /// See [`SmartUsage::node_count`].
+    fn dyn_node_count(&self) -> usize;
+
+    /// See [`SmartUsage::node_usage`].
+    fn dyn_node_usage(&self, writer: &mut dyn Write) -> Result<(), Error>;
+
+    /// See [`SmartUsage::smart_usage`].
+    fn dyn_smart_usage(&self, writer: &mut dyn Write, optional: bool, depth: usize) -> Result<(), Error>;
+}
+
+impl<T> DynSmartUsage for T
+where
+    T: SmartUsage,
+{
+    fn dyn_node_count(&self) -> usize { self.node_count() }
+
+    fn dyn_node_usage(&self, mut writer: &mut dyn Write) -> Result<(), Error> { self.node_usage(&mut writer) }
+
+    fn dyn_smart_usage(&self, mut writer: &mut dyn Write, optional: bool, depth: usize) -> Result<(), Error> {
+        self.smart_usage(&mut writer, optional, depth)
+    }
+}
+
+/// Usages of several boxed usages, one after the other.
+#[derive(Debug, Clone)]
+pub struct BoxedUsages {
+    pub(crate) usages: Vec<BoxedUsage>,
+    pub(crate) index: usize,
+}
+
+impl BoxedUsages {
+    fn current(&self) -> Option<&BoxedUsage> { self.usages[self.index..].iter().find(|u| u.is_next()) }
+
+    fn advance(&mut self) -> Option<&mut BoxedUsage> {
+        while self.usages.get(self.index).is_some_and(|u| !u.is_next()) {
+            self.index += 1;
+        }
+        self.usages.get_mut(self.index)
+    }
+}
+
+impl MultipleUsage for BoxedUsages {
+    fn usage_next<W: Write>(&mut self, writer: &mut W) -> Option<Result<(), Error>> { self.advance()?.usage_next(writer) }
+
+    fn usage_next_typed<W: Write>(&mut self, writer: &mut W) -> Option<Result<(), Error>> { self.advance()?.usage_next_typed(writer) }
+
+    fn is_next(&self) -> bool { self.current().is_some() }
+
+    fn usage_description(&self) -> Option<&'static str> { self.current()?.usage_description() }
+}
@@ -29,5 +29,16 @@ where
         self.usage.usage_next(writer)
     }
 
+    fn usage_next_typed<W: Write>(&mut self, writer: &mut W) -> Option<Result<(), std::fmt::Error>> {
+        if self.usage.is_next() {
+            if let Err(e) = self.prefix.usage_typed(writer) {
+                return Some(Err(e));
+            }
+        }
+        self.usage.usage_next_typed(writer)
+    }
+
     fn is_next(&self) -> bool { self.usage.is_next() }
+
+    fn usage_description(&self) -> Option<&'static str> { self.usage.usage_description() }
 }
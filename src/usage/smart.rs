@@ -0,0 +1,115 @@
+use std::fmt::{Error, Write};
+
+use crate::{ChildUsage, SingleUsage};
+
+/// Compact usage of a parser tree, similar to Brigadier's `getSmartUsage`.
+///
+/// Alternative branches are collapsed into `(a|b)`, branches that are optional
+/// because their parent can be executed are put between brackets:
+/// `foo (add|remove) <player> [<amount>]`.
+pub trait SmartUsage {
+    /// Number of parser nodes this type consists of.
+    fn node_count(&self) -> usize;
+
+    /// Write the usage of each node (without children) separated by `|`.
+    fn node_usage<W: Write>(&self, writer: &mut W) -> Result<(), Error>;
+
+    /// Write the smart usage of this type, rendering at most `depth` levels of
+    /// children.
+    fn smart_usage<W: Write>(&self, writer: &mut W, optional: bool, depth: usize) -> Result<(), Error>;
+
+    /// Returns the smart usage as a [`String`].
+    fn smart_usage_string(&self, depth: usize) -> String {
+        let mut usage = String::new();
+        let _ = self.smart_usage(&mut usage, false, depth);
+        usage
+    }
+}
+
+/// Parser without children, its smart usage is its [`ChildUsage`].
+pub(crate) trait LeafUsage: ChildUsage {}
+
+impl<T> SmartUsage for T
+where
+    T: LeafUsage,
+{
+    fn node_count(&self) -> usize { 1 }
+
+    fn node_usage<W: Write>(&self, writer: &mut W) -> Result<(), Error> { self.usage_child().usage(writer) }
+
+    fn smart_usage<W: Write>(&self, writer: &mut W, optional: bool, _depth: usize) -> Result<(), Error> {
+        if optional {
+            writer.write_char('[')?;
+        }
+        self.usage_child().usage(writer)?;
+        if optional {
+            writer.write_char(']')?;
+        }
+        Ok(())
+    }
+}
+
+/// Write the smart usage of a node, `children` are optional if the node is
+/// `executable`.
+pub(crate) fn smart_node<N, C, W>(
+    node: &N,
+    children: &C,
+    executable: bool,
+    writer: &mut W,
+    optional: bool,
+    depth: usize,
+) -> Result<(), Error>
+where
+    N: SingleUsage,
+    C: SmartUsage,
+    W: Write,
+{
+    if optional {
+        writer.write_char('[')?;
+    }
+    node.usage(writer)?;
+    if depth > 0 {
+        writer.write_char(' ')?;
+        children.smart_usage(writer, executable, depth - 1)?;
+    }
+    if optional {
+        writer.write_char(']')?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use crate::parsers::help::ThenHelp;
+    use crate::{integer_i32, literal, BuildExecute, BuildPropagate, Execute, ExecuteBranch, SmartUsage, Then, ThenAll};
+
+    #[test]
+    fn test_smart_usage() {
+        let amount = || integer_i32("amount").build_propagate(|_: (), (_, _)| Ok::<(), Infallible>(()));
+        let remove = || literal::<()>("remove").then(integer_i32("player").then(amount()).build_exec(|_: (), _| Ok::<(), Infallible>(())));
+        let parser = literal("foo")
+            .then(literal("add").then(integer_i32("player").then(amount())))
+            .then(remove())
+            .build_exec(|_: ()| Ok::<(), Infallible>(()))
+            .help("Test description")
+            .build_exec(|_: (), _| Ok::<(), Infallible>(()));
+
+        assert_eq!(("", ()), parser.execute((), "foo add 1 2").unwrap());
+        assert_eq!("foo", parser.smart_usage_string(0));
+        assert_eq!("foo [add|remove]", parser.smart_usage_string(3));
+        assert_eq!("remove <player> [<amount>]", remove().smart_usage_string(3));
+        assert_eq!("remove <player>", remove().smart_usage_string(1));
+        assert_eq!(
+            "add <player> <amount>",
+            literal::<()>("add")
+                .then(integer_i32::<()>("player").then(amount()))
+                .smart_usage_string(3)
+        );
+        let parser = literal::<()>("foo")
+            .then(literal::<()>("add").then(integer_i32("player").build_exec(|_: (), player| Ok::<_, Infallible>(player))))
+            .then_all(Vec::<Box<dyn ExecuteBranch<(), i32>>>::new());
+        assert_eq!("foo add <player>", parser.smart_usage_string(3));
+    }
+}